@@ -0,0 +1,209 @@
+//! Generates the solution registry for the `runner` binary.
+//!
+//! Every scaffolded day in `src/bin/{year}_{day}.rs` is included as a module of the runner, so that
+//! all days can be run in-process without a cargo invocation per day.
+//! Also records the version of the compiler and the target CPU flags, which are stored alongside benchmark results, and
+//! generates a test for every case of the examples manifests in `data/{year}/examples/{day}.json`.
+use std::{collections::HashMap, env, fmt::Write, fs, path::Path, process::Command};
+use tinyjson::JsonValue;
+
+fn main() {
+    println!("cargo::rerun-if-changed=src/bin");
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let rustc_version = Command::new(rustc)
+        .arg("-V")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    println!(
+        "cargo::rustc-env=AOC_RUSTC_VERSION={}",
+        rustc_version.trim()
+    );
+    println!("cargo::rerun-if-env-changed=CARGO_ENCODED_RUSTFLAGS");
+    println!("cargo::rustc-env=AOC_TARGET_CPU={}", target_cpu_flags());
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let stem = path.file_stem()?.to_str()?;
+            let (year, day) = stem.split_once('_')?;
+            let is_day = path.extension()? == "rs"
+                && year.len() == 4
+                && day.len() == 2
+                && year.bytes().chain(day.bytes()).all(|b| b.is_ascii_digit());
+            is_day.then(|| (stem.to_string(), path.to_string_lossy().into_owned()))
+        })
+        .collect();
+
+    days.sort_unstable();
+
+    let mut registry = String::new();
+
+    for (bin, path) in &days {
+        // lints are reported when the day is built as its own binary, don't repeat them here.
+        writeln!(registry, "#[path = {path:?}]").unwrap();
+        writeln!(registry, "#[allow(warnings, clippy::all)]").unwrap();
+        writeln!(registry, "mod day_{bin};").unwrap();
+    }
+
+    writeln!(
+        registry,
+        "const SOLUTIONS: advent_of_code::template::Registry = advent_of_code::template::Registry::new(&[{}]);",
+        days.iter()
+            .map(|(bin, _)| format!("day_{bin}::SOLUTION"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+    .unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+
+    let examples_dir = Path::new(&out_dir).join("examples");
+    fs::create_dir_all(&examples_dir).unwrap();
+
+    for (bin, _) in &days {
+        let (year, day) = bin.split_once('_').unwrap();
+        let data_dir = Path::new(&manifest_dir).join("data").join(year);
+
+        // NOTE: watch the folder instead of the manifest, a missing file would re-run the script on every build.
+        if data_dir.join("examples").is_dir() {
+            println!("cargo::rerun-if-changed=data/{year}/examples");
+        }
+
+        let manifest_path = data_dir.join("examples").join(format!("{day}.json"));
+        let tests = match fs::read_to_string(&manifest_path) {
+            Ok(contents) => example_tests(&contents).unwrap_or_else(|e| {
+                format!(
+                    "compile_error!({:?});",
+                    format!("{}: {e}", manifest_path.display())
+                )
+            }),
+            Err(_) => String::new(),
+        };
+
+        // `solution!` includes the tests of a day by its unpadded number, e.g. `2025_1.rs`.
+        let file_name = format!("{year}_{}.rs", day.trim_start_matches('0'));
+        fs::write(examples_dir.join(file_name), tests).unwrap();
+    }
+}
+
+/// Generate a test for every part with an expected answer of every case in an examples manifest.
+fn example_tests(manifest: &str) -> Result<String, String> {
+    let json: JsonValue = manifest.parse().map_err(|_| "not valid JSON file.")?;
+    let cases = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?;
+
+    let mut names: Vec<&String> = cases.keys().collect();
+    names.sort_unstable();
+
+    let mut tests = String::new();
+    let mut idents: HashMap<String, &String> = HashMap::new();
+
+    for name in names {
+        let case = cases[name]
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or(format!("expected example `{name}` to be an object."))?;
+
+        // NOTE: mirrors the validation of `ExampleManifest`, so that a manifest that fails at runtime fails the build.
+        case.get("file")
+            .and_then(|file| file.get::<String>())
+            .ok_or(format!("expected `{name}.file` to be a string."))?;
+
+        if let Some(params) = case.get("params") {
+            let params = params
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("expected `{name}.params` to be an object."))?;
+
+            for (param, value) in params {
+                if !matches!(value, JsonValue::String(_) | JsonValue::Number(_)) {
+                    return Err(format!(
+                        "expected `{name}.params.{param}` to be a string or a number."
+                    ));
+                }
+            }
+        }
+
+        let mut parts: Vec<u8> = case
+            .iter()
+            .filter(|(key, _)| *key != "file" && *key != "params")
+            .map(|(key, answer)| {
+                let part = key
+                    .strip_prefix("part_")
+                    .and_then(|part| part.parse::<u8>().ok())
+                    .ok_or(format!(
+                        "expected `{name}.{key}` to be a part, e.g. `part_1`."
+                    ))?;
+                answer
+                    .get::<String>()
+                    .ok_or(format!("expected `{name}.{key}` to be a string."))?;
+                Ok(part)
+            })
+            .collect::<Result<_, String>>()?;
+        parts.sort_unstable();
+
+        let ident: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        if let Some(other) = idents.insert(ident.clone(), name) {
+            return Err(format!(
+                "examples `{other}` and `{name}` both map to the test name `example_{ident}`, rename one of them."
+            ));
+        }
+
+        for part in parts {
+            writeln!(tests, "#[test]").unwrap();
+            writeln!(tests, "fn example_{ident}_part_{part}() {{").unwrap();
+            writeln!(
+                tests,
+                "    advent_of_code::template::runner::check_example(PUZZLE, PARTS, input_params(), {name:?}, {part});"
+            )
+            .unwrap();
+            writeln!(tests, "}}").unwrap();
+        }
+    }
+
+    Ok(tests)
+}
+
+/// The `target-cpu` and `target-feature` codegen flags of the build, e.g. `target-cpu=native`, or `default` if none.
+fn target_cpu_flags() -> String {
+    let rustflags = env::var("CARGO_ENCODED_RUSTFLAGS").unwrap_or_default();
+
+    let mut flags: Vec<String> = vec![];
+    let mut args = rustflags.split('\x1f');
+
+    while let Some(arg) = args.next() {
+        // NOTE: codegen flags are passed either as `-Ctarget-cpu=native` or as `-C target-cpu=native`.
+        let flag = match arg.strip_prefix("-C") {
+            Some("") => args.next().unwrap_or_default(),
+            Some(flag) => flag,
+            None => continue,
+        };
+
+        if flag.starts_with("target-cpu=") || flag.starts_with("target-feature=") {
+            flags.push(flag.to_string());
+        }
+    }
+
+    if flags.is_empty() {
+        "default".into()
+    } else {
+        flags.join(" ")
+    }
+}
@@ -1,6 +1,6 @@
 use rayon::prelude::*;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2025, 2);
 
 #[inline]
 fn is_palindrome_number(id: u64) -> bool {
@@ -114,13 +114,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1227775554));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4174379265));
     }
 }
@@ -1,6 +1,6 @@
 use rayon::{iter::ParallelIterator, str::ParallelString};
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2025, 3);
 
 #[inline]
 fn find_max_joltage(line: &str) -> Option<u64> {
@@ -134,14 +134,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         println!("Result: {:?}", result);
         assert_eq!(result, Some(357));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         println!("Result: {:?}", result);
         assert_eq!(result, Some(3121910778619));
     }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(4);
+advent_of_code::solution!(2025, 4, setup = make_padded_grid);
 
 // Count matching neighbors directly without collecting into array first
 // This eliminates the overhead of array building and Option unwrapping
@@ -147,7 +147,7 @@ fn has_4_or_fewer_neighbors(grid: &[Vec<bool>], row: usize, col: usize) -> bool
 }
 
 /// Build a grid with border padding to avoid bounds checking
-fn make_padded_grid(input: &str) -> Vec<Vec<bool>> {
+pub fn make_padded_grid(input: &str) -> Vec<Vec<bool>> {
     let mut grid: Vec<Vec<bool>> = input
         .lines()
         .map(|line| {
@@ -165,8 +165,7 @@ fn make_padded_grid(input: &str) -> Vec<Vec<bool>> {
     grid
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let grid = make_padded_grid(input);
+pub fn part_one(grid: Vec<Vec<bool>>) -> Option<u64> {
     let col_count = grid[0].len();
 
     // Start inside the grid "border" to avoid bounds checking
@@ -181,9 +180,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(count as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    // Build grid with border padding
-    let mut grid = make_padded_grid(input);
+pub fn part_two(mut grid: Vec<Vec<bool>>) -> Option<u64> {
     let col_count = grid[0].len();
 
     let initial_rolls = grid
@@ -228,14 +225,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(make_padded_grid(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         println!("Result: {:?}", result);
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(make_padded_grid(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         println!("Result: {:?}", result);
         assert_eq!(result, Some(43));
     }
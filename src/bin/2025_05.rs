@@ -1,4 +1,4 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(2025, 5);
 
 fn capture_id_ranges_and_ingredient_ids(input: &str) -> (Vec<(u64, u64)>, Vec<u64>) {
     input
@@ -112,14 +112,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         println!("Result: {:?}", result);
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         println!("Result: {:?}", result);
         assert_eq!(result, Some(14));
     }
@@ -2,7 +2,7 @@
 
 use std::simd::{Simd, cmp::SimdPartialEq};
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2025, 6);
 
 // SIMD lane size - typically 16, 32, or 64 bytes depending on CPU
 const SIMD_LANE_SIZE: usize = 64;
@@ -304,14 +304,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         println!("Result: {:?}", result);
         assert_eq!(result, Some(4277556));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3263827));
     }
 }
@@ -1,4 +1,6 @@
-advent_of_code::solution!(7);
+advent_of_code::solution!(2025, 7, variants = {
+    1 => [part_one_old],
+});
 
 /// Bit-packed beam state using 3 u64s (supports up to 192 positions, actual: 141)
 #[derive(Clone, Copy)]
@@ -132,68 +134,21 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         println!("Result: {:?}", result);
         assert_eq!(result, Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         println!("Result: {:?}", result);
         assert_eq!(result, Some(40));
     }
 
     #[test]
-    fn benchmark_old_vs_new() {
-        use std::time::Instant;
-
-        let input = advent_of_code::template::read_file("inputs", DAY);
-        let lines_count = input.lines().count();
-        let width = input.lines().next().unwrap().len();
-
-        // Warmup
-        let _ = part_one_old(&input);
-        let _ = part_one(&input);
-
-        // Benchmark OLD implementation (Vec<bool>)
-        let iterations = 100;
-        let start_old = Instant::now();
-        for _ in 0..iterations {
-            let _ = part_one_old(&input);
-        }
-        let duration_old = start_old.elapsed() / iterations;
-
-        // Benchmark NEW implementation (bit-packed)
-        let start_new = Instant::now();
-        for _ in 0..iterations {
-            let _ = part_one(&input);
-        }
-        let duration_new = start_new.elapsed() / iterations;
-
-        // Verify results match
-        let result_old = part_one_old(&input);
-        let result_new = part_one(&input);
-        assert_eq!(result_old, result_new, "Results don't match!");
-
-        println!("\n=== Day 7 Part 1 Performance Comparison ===");
-        println!("Grid size: {} lines × {} columns", lines_count, width);
-        println!("Iterations: {}", iterations);
-        println!("\nOLD (Vec<bool>):  {:?}", duration_old);
-        println!("NEW (bit-packed): {:?}", duration_new);
-        println!(
-            "\nSpeedup: {:.2}x",
-            duration_old.as_secs_f64() / duration_new.as_secs_f64()
-        );
-        println!("Time saved: {:?} per run", duration_old - duration_new);
-
-        // Memory comparison
-        println!("\n=== Memory Usage ===");
-        println!("OLD: {} bytes (Vec<bool> + metadata)", width + 24);
-        println!("NEW: 24 bytes (3 × u64)");
-        println!(
-            "Savings: {:.1}% reduction",
-            100.0 * (1.0 - 24.0 / (width + 24) as f64)
-        );
+    fn test_part_one_old() {
+        let result = part_one_old(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(21));
     }
 }
@@ -1,6 +1,7 @@
+use advent_of_code::template::Context;
 use std::{collections::HashMap, hash::Hash};
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2025, 8, params = { edges = 1000 });
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Copy)]
 pub struct JunctionBox {
@@ -125,9 +126,9 @@ fn parse_junctions(input: &str) -> Vec<JunctionBox> {
         .collect::<Vec<JunctionBox>>()
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    // NOTE: Important to change this for the test vs the example!
-    let number_of_edges_to_process = 10;
+pub fn part_one(input: &str, ctx: &Context) -> Option<u64> {
+    // NOTE: the example connects 10 pairs, the real input 1000.
+    let number_of_edges_to_process: usize = ctx.param("edges")?;
 
     let all_junctions = parse_junctions(input);
     let n = all_junctions.len();
@@ -221,20 +222,3 @@ pub fn part_two(input: &str) -> Option<u64> {
         None
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(40));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(25272));
-    }
-}
@@ -1,6 +1,8 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(2025, 9, variants = {
+    2 => [part_two_custom, part_two_rstar],
+});
 
 // Spatial index for polygon edges to speed up point-in-polygon queries
 struct SpatialIndex {
@@ -574,102 +576,20 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(50));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(24));
     }
 
     #[test]
-    fn benchmark_implementations() {
-        use std::time::Instant;
-
-        let input = advent_of_code::template::read_file("inputs", DAY);
-        let points_count = input.lines().count();
-
-        println!("\n=== Day 9 Part 2 Implementation Comparison ===");
-        println!("Points: {}", points_count);
-        println!("Pairs to check: {}", points_count * (points_count - 1) / 2);
-
-        // Warmup
-        let _ = part_two(&input);
-        let _ = part_two_geo_crate(&input);
-        let _ = part_two_rstar(&input);
-
-        let iterations = 10;
-
-        // Benchmark 1: Current implementation (custom single-pass)
-        let start = Instant::now();
-        for _ in 0..iterations {
-            let _ = part_two(&input);
-        }
-        let time_custom = start.elapsed() / iterations;
-
-        // Benchmark 2: Geo crate
-        let start = Instant::now();
-        for _ in 0..iterations {
-            let _ = part_two_geo_crate(&input);
-        }
-        let time_geo = start.elapsed() / iterations;
-
-        // Benchmark 3: R-tree spatial index
-        let start = Instant::now();
-        for _ in 0..iterations {
-            let _ = part_two_rstar(&input);
-        }
-        let time_rstar = start.elapsed() / iterations;
-
-        // Verify all produce same result
-        let result_custom = part_two(&input);
-        let result_geo = part_two_geo_crate(&input);
-        let result_rstar = part_two_rstar(&input);
-
-        assert_eq!(result_custom, result_geo, "Geo crate result doesn't match!");
-        assert_eq!(result_custom, result_rstar, "R-tree result doesn't match!");
-
-        println!(
-            "\n=== Results (averaged over {} iterations) ===",
-            iterations
-        );
-        println!("1. Custom (single-pass):   {:?}", time_custom);
-        println!("2. Geo crate:              {:?}", time_geo);
-        println!("3. R-star spatial index:   {:?}", time_rstar);
-
-        let baseline = time_custom.as_secs_f64();
-        println!("\n=== Speedup vs Custom ===");
-        println!(
-            "Geo crate:   {:.2}x {}",
-            baseline / time_geo.as_secs_f64(),
-            if time_geo < time_custom {
-                "faster ⚡"
-            } else {
-                "slower"
-            }
-        );
-        println!(
-            "R-star:      {:.2}x {}",
-            baseline / time_rstar.as_secs_f64(),
-            if time_rstar < time_custom {
-                "faster ⚡"
-            } else {
-                "slower"
-            }
-        );
-
-        // Find the fastest
-        let fastest_time = time_custom.min(time_geo).min(time_rstar);
-        let fastest_name = if fastest_time == time_custom {
-            "Custom single-pass"
-        } else if fastest_time == time_geo {
-            "Geo crate"
-        } else {
-            "R-star spatial index"
-        };
-
-        println!("\n🏆 Winner: {} ({:?})", fastest_name, fastest_time);
+    fn test_part_two_variants() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        assert_eq!(part_two_custom(&input), Some(24));
+        assert_eq!(part_two_rstar(&input), Some(24));
     }
 }
@@ -1,4 +1,7 @@
-advent_of_code::solution!(10);
+advent_of_code::solution!(2025, 10, variants = {
+    1 => [part_one_all_combinations],
+    2 => [part_two_bfs, part_two_a_star],
+});
 
 /// Solves a system of linear equations over GF(2) using Gaussian elimination.
 /// Returns the minimum number of button presses needed.
@@ -105,6 +108,15 @@ fn solve_gf2(buttons: &[Vec<bool>], target: &[bool]) -> Option<u64> {
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
+    sum_light_presses(input, solve_with_masks)
+}
+
+fn part_one_all_combinations(input: &str) -> Option<u64> {
+    sum_light_presses(input, solve_all_possible_button_combinations)
+}
+
+/// Sum the minimum presses to reach the indicator light state of every machine, solved by `solver`.
+fn sum_light_presses(input: &str, solver: fn(&[Vec<bool>], &[bool]) -> Option<u64>) -> Option<u64> {
     let mut total = 0;
     let machines = input.lines().collect::<Vec<&str>>();
     // For each machine (line)
@@ -134,21 +146,23 @@ pub fn part_one(input: &str) -> Option<u64> {
             })
             .collect();
 
-        // Use bit-packed solver for machines with ≤32 lights (all inputs qualify)
-        let min_presses = if num_lights <= 32 {
-            let button_masks: Vec<u32> = buttons.iter().map(|b| button_to_mask(b)).collect();
-            let target_mask = target_to_mask(&target_light_state);
-            solve_bitpacked(&button_masks, target_mask)?
-        } else {
-            // Fall back to Vec<bool> version for larger machines (shouldn't happen)
-            solve_all_possible_button_combinations(&buttons, &target_light_state)?
-        };
-
-        total += min_presses;
+        total += solver(&buttons, &target_light_state)?;
     }
     Some(total)
 }
 
+fn solve_with_masks(buttons: &[Vec<bool>], target: &[bool]) -> Option<u64> {
+    // Use bit-packed solver for machines with ≤32 lights (all inputs qualify)
+    if target.len() <= 32 {
+        let button_masks: Vec<u32> = buttons.iter().map(|b| button_to_mask(b)).collect();
+        let target_mask = target_to_mask(target);
+        solve_bitpacked(&button_masks, target_mask)
+    } else {
+        // Fall back to Vec<bool> version for larger machines (shouldn't happen)
+        solve_all_possible_button_combinations(buttons, target)
+    }
+}
+
 /// Try all possible combinations of button presses (0 or 1 times each)
 /// and find the minimum number of presses needed.
 fn solve_all_possible_button_combinations(buttons: &[Vec<bool>], target: &[bool]) -> Option<u64> {
@@ -518,6 +532,20 @@ fn recursive_parity_solve(buttons: &[Vec<i64>], target: &[i64]) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
+    // Solve using recursive parity (even/odd) approach
+    sum_joltage_presses(input, recursive_parity_solve)
+}
+
+fn part_two_bfs(input: &str) -> Option<u64> {
+    sum_joltage_presses(input, bfs_solve)
+}
+
+fn part_two_a_star(input: &str) -> Option<u64> {
+    sum_joltage_presses(input, a_star_solve)
+}
+
+/// Sum the minimum presses to reach the joltage levels of every machine, solved by `solver`.
+fn sum_joltage_presses(input: &str, solver: fn(&[Vec<i64>], &[i64]) -> Option<u64>) -> Option<u64> {
     let mut total = 0;
 
     for machine in input.lines() {
@@ -549,10 +577,7 @@ pub fn part_two(input: &str) -> Option<u64> {
             })
             .collect();
 
-        // Solve using recursive parity (even/odd) approach
-        let min_presses = recursive_parity_solve(&buttons, &target)?;
-
-        total += min_presses;
+        total += solver(&buttons, &target)?;
     }
 
     Some(total)
@@ -563,13 +588,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(7));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(33));
     }
 
@@ -592,74 +617,16 @@ mod tests {
     }
 
     #[test]
-    fn benchmark_old_vs_new() {
-        use std::time::Instant;
-
-        let input = advent_of_code::template::read_file("inputs", DAY);
-
-        // Parse all machines once
-        let mut machines_data = Vec::new();
-        for machine in input.lines() {
-            let (indicator_lights, rest) = machine.split_once("]").unwrap();
-            let indicator_lights = indicator_lights.strip_prefix("[").unwrap();
-            let target_light_state: Vec<bool> =
-                indicator_lights.chars().map(|c| c == '#').collect();
-            let num_lights = target_light_state.len();
-
-            let (buttons_str, _) = rest.split_once("{").unwrap();
-            let buttons: Vec<Vec<bool>> = buttons_str
-                .split_whitespace()
-                .filter(|s| s.starts_with('('))
-                .map(|b| {
-                    let indices: Vec<usize> = b
-                        .trim_matches(|c| c == '(' || c == ')')
-                        .split(',')
-                        .map(|n| n.parse().unwrap())
-                        .collect();
-                    let mut vec = vec![false; num_lights];
-                    for i in indices {
-                        vec[i] = true;
-                    }
-                    vec
-                })
-                .collect();
-
-            machines_data.push((target_light_state, buttons));
-        }
+    fn test_part_one_all_combinations() {
+        let result =
+            part_one_all_combinations(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(7));
+    }
 
-        // Benchmark OLD implementation (Vec<bool>)
-        let start_old = Instant::now();
-        let mut total_old = 0;
-        for (target, buttons) in &machines_data {
-            if let Some(presses) = solve_all_possible_button_combinations(buttons, target) {
-                total_old += presses;
-            }
-        }
-        let duration_old = start_old.elapsed();
-
-        // Benchmark NEW implementation (bit-packed)
-        let start_new = Instant::now();
-        let mut total_new = 0;
-        for (target, buttons) in &machines_data {
-            let button_masks: Vec<u32> = buttons.iter().map(|b| button_to_mask(b)).collect();
-            let target_mask = target_to_mask(target);
-            if let Some(presses) = solve_bitpacked(&button_masks, target_mask) {
-                total_new += presses;
-            }
-        }
-        let duration_new = start_new.elapsed();
-
-        // Results should match
-        assert_eq!(total_old, total_new, "Results don't match!");
-
-        println!("\n=== Day 10 Part 1 Performance Comparison ===");
-        println!("Total machines: {}", machines_data.len());
-        println!("\nOLD (Vec<bool>):  {:?}", duration_old);
-        println!("NEW (bit-packed): {:?}", duration_new);
-        println!(
-            "\nSpeedup: {:.2}x",
-            duration_old.as_secs_f64() / duration_new.as_secs_f64()
-        );
-        println!("Time saved: {:?}", duration_old - duration_new);
+    #[test]
+    fn test_part_two_search_variants() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        assert_eq!(part_two_bfs(&input), Some(33));
+        assert_eq!(part_two_a_star(&input), Some(33));
     }
 }
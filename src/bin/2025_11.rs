@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(2025, 11);
 
 /// Recursive DFS function with backtracking
 fn dfs_count_paths<'a>(
@@ -152,33 +152,3 @@ pub fn part_two(input: &str) -> Option<u64> {
 
     Some(total_paths)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let test_input = "svr: aaa bbb
-        aaa: fft
-        fft: ccc
-        bbb: tty
-        tty: ccc
-        ccc: ddd eee
-        ddd: hub
-        hub: fff
-        eee: dac
-        dac: fff
-        fff: ggg hhh
-        ggg: out
-        hhh: out";
-        let result = part_two(&test_input);
-        assert_eq!(result, Some(2));
-    }
-}
@@ -1,7 +1,7 @@
 use rayon::prelude::*;
 use std::collections::{HashSet, VecDeque};
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(2025, 12);
 
 // ============================================================================
 // BIT-PACKED REGION (for regions <= 64 cells, e.g., 8x8, 4x16, etc.)
@@ -704,13 +704,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
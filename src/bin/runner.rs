@@ -0,0 +1,33 @@
+// NOTE: features used by any solution have to be enabled here as well, since every day is a module of this binary.
+#![feature(portable_simd)]
+
+//! Runs all scaffolded days in a single process.
+//! Invoked by the `all` and `time` commands, e.g. `runner --time 2025/01 2025/02 2025/03`.
+
+use advent_of_code::template::{PuzzleId, runner::run_days};
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+fn main() {
+    let mut args = pico_args::Arguments::from_env();
+
+    // NOTE: `--time`, `--json` and `--assert-no-alloc` are read by the runner when executing a part.
+    args.contains("--time");
+    args.contains("--json");
+    args.contains("--assert-no-alloc");
+
+    let mut puzzles: Vec<PuzzleId> = vec![];
+
+    loop {
+        match args.opt_free_from_str() {
+            Ok(Some(puzzle)) => puzzles.push(puzzle),
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    run_days(&SOLUTIONS, &puzzles);
+}
@@ -1,79 +1,267 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, answer, baseline, compare, download, examples, history, read, scaffold, solve, time,
+    verify, watch,
+};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        DEFAULT_TOLERANCE, Day, PuzzleId, Year, parse_param, parse_tolerance,
+    };
+    use std::{path::PathBuf, process};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
+            count_alloc: bool,
             submit: Option<u8>,
+            example: Option<String>,
+            input: Option<String>,
+            params: Vec<String>,
+            watch: bool,
+            trace: Option<PathBuf>,
         },
         All {
+            year: Year,
             release: bool,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            count_alloc: bool,
+            assert_no_alloc: bool,
+            check: bool,
+            tolerance: f64,
+            baseline: Option<String>,
+            report: Option<PathBuf>,
+        },
+        History {
+            puzzle: PuzzleId,
+        },
+        Compare {
+            puzzle: PuzzleId,
+            params: Vec<String>,
+        },
+        Answer {
+            puzzle: PuzzleId,
+            part: u8,
+            answer: String,
+        },
+        Verify {
+            year: Year,
+            day: Option<Day>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Parameter overrides, e.g. `--param edges=1000`. Validated here, passed on to the solution as is.
+    fn params(args: &mut pico_args::Arguments) -> Result<Vec<String>, pico_args::Error> {
+        args.values_from_fn("--param", |param| {
+            parse_param(param).map(|_| param.to_string())
+        })
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // NOTE: options have to be parsed before free arguments. Falls back to `AOC_YEAR`, see `.cargo/config.toml`.
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+        let year = || {
+            year.or_else(Year::from_env)
+                .ok_or("no year specified, pass `--year <year>` or set `AOC_YEAR`.")
+        };
+        let puzzle = |day: Day| -> Result<PuzzleId, String> {
+            let year = year()?;
+            PuzzleId::new(year, day).ok_or_else(|| {
+                format!(
+                    "{year} has no puzzle on day {day}, its calendar has {} days.",
+                    year.calendar().days
+                )
+            })
+        };
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year: year()?,
                 release: args.contains("--release"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let count_alloc = args.contains("--count-alloc");
+                let assert_no_alloc = args.contains("--assert-no-alloc");
+                let check = args.contains("--check");
+                let tolerance: Option<f64> =
+                    args.opt_value_from_fn("--tolerance", parse_tolerance)?;
+
+                let baseline: Option<String> = args.opt_value_from_str("--baseline")?;
+                let report: Option<PathBuf> = args.opt_value_from_str("--report")?;
+
+                if tolerance.is_some() && !check && baseline.is_none() {
+                    return Err("`--tolerance` requires `--check` or `--baseline`.".into());
+                }
+
+                if report.is_some() && baseline.is_none() {
+                    return Err("`--report` requires `--baseline`.".into());
+                }
+
+                if check && store {
+                    return Err("`--check` can't be combined with `--store`.".into());
+                }
+
+                if baseline.is_some() && (check || store || all || count_alloc || assert_no_alloc) {
+                    return Err(
+                        "`--baseline` can't be combined with `--check`, `--store`, `--all`, `--count-alloc` or `--assert-no-alloc`."
+                            .into(),
+                    );
+                }
 
                 AppArguments::Time {
+                    year: year()?,
                     all,
-                    day: args.opt_free_from_str()?,
+                    day: args
+                        .opt_free_from_str()?
+                        .map(puzzle)
+                        .transpose()?
+                        .map(|p| p.day),
                     store,
+                    count_alloc,
+                    assert_no_alloc,
+                    check,
+                    tolerance: tolerance.unwrap_or(DEFAULT_TOLERANCE),
+                    baseline,
+                    report,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            Some("history") => AppArguments::History {
+                puzzle: puzzle(args.free_from_str()?)?,
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            Some("compare") => {
+                let params = params(&mut args)?;
+
+                AppArguments::Compare {
+                    puzzle: puzzle(args.free_from_str()?)?,
+                    params,
+                }
+            }
+            Some("answer") => AppArguments::Answer {
+                puzzle: puzzle(args.free_from_str()?)?,
+                part: args.free_from_str()?,
+                answer: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+            Some("verify") => AppArguments::Verify {
+                year: year()?,
+                day: args
+                    .opt_free_from_str()?
+                    .map(puzzle)
+                    .transpose()?
+                    .map(|p| p.day),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("download") => AppArguments::Download {
+                puzzle: puzzle(args.free_from_str()?)?,
             },
+            Some("read") => AppArguments::Read {
+                puzzle: puzzle(args.free_from_str()?)?,
+            },
+            Some("examples") => {
+                let overwrite = args.contains("--overwrite");
+
+                AppArguments::Examples {
+                    puzzle: puzzle(args.free_from_str()?)?,
+                    overwrite,
+                }
+            }
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+
+                AppArguments::Scaffold {
+                    puzzle: puzzle(args.free_from_str()?)?,
+                    download,
+                    overwrite,
+                }
+            }
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let count_alloc = args.contains("--count-alloc");
+                let example: Option<String> = args.opt_value_from_str("--example")?;
+                let input: Option<String> = args.opt_value_from_str("--input")?;
+                let watch = args.contains("--watch");
+                let trace: Option<PathBuf> = args.opt_value_from_str("--trace")?;
+                let params = params(&mut args)?;
+
+                if input.is_some() && example.is_some() {
+                    return Err("`--input` can't be combined with `--example`.".into());
+                }
+
+                if submit.is_some() && (example.is_some() || input.is_some() || !params.is_empty())
+                {
+                    return Err(
+                        "`--submit` can't be combined with `--example`, `--input` or `--param`."
+                            .into(),
+                    );
+                }
+
+                if dhat && count_alloc {
+                    return Err("`--dhat` can't be combined with `--count-alloc`.".into());
+                }
+
+                if watch
+                    && (submit.is_some()
+                        || dhat
+                        || count_alloc
+                        || example.is_some()
+                        || input.is_some()
+                        || trace.is_some())
+                {
+                    return Err(
+                        "`--watch` can't be combined with `--submit`, `--dhat`, `--count-alloc`, `--example`, `--input` or `--trace`."
+                            .into(),
+                    );
+                }
+
+                AppArguments::Solve {
+                    puzzle: puzzle(args.free_from_str()?)?,
+                    release,
+                    dhat,
+                    count_alloc,
+                    submit,
+                    example,
+                    input,
+                    params,
+                    watch,
+                    trace,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -102,38 +290,97 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All { year, release } => all::handle(year, release),
+            AppArguments::Time {
+                year,
+                day,
+                tolerance,
+                baseline: Some(rev),
+                report,
+                ..
+            } => baseline::handle(year, day, &rev, tolerance, report.as_deref()),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                count_alloc,
+                assert_no_alloc,
+                check,
+                tolerance,
+                baseline: None,
+                ..
+            } => time::handle(
+                year,
                 day,
+                all,
+                store,
+                count_alloc,
+                assert_no_alloc,
+                check.then_some(tolerance),
+            ),
+            AppArguments::History { puzzle } => history::handle(puzzle),
+            AppArguments::Compare { puzzle, params } => compare::handle(puzzle, &params),
+            AppArguments::Answer {
+                puzzle,
+                part,
+                answer,
+            } => answer::handle(puzzle, part, &answer),
+            AppArguments::Verify { year, day } => verify::handle(year, day),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples { puzzle, overwrite } => examples::handle(puzzle, overwrite),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
+                release,
+                params,
+                watch: true,
+                ..
+            } => watch::handle(puzzle, release, &params),
+            AppArguments::Solve {
+                puzzle,
+                release,
+                dhat,
+                count_alloc,
+                submit,
+                example,
+                input,
+                params,
+                watch: false,
+                trace,
+            } => solve::handle(
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                example.as_deref(),
+                input.as_deref(),
+                &params,
+                count_alloc,
+                trace.as_deref(),
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        scaffold::handle(puzzle, false);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run on a day of december with a puzzle. \
+                            Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,158 @@
+/// Lightweight allocation counting, enabled with the `count-alloc` feature.
+/// Unlike DHAT, the counters are cheap enough to stay active while benching.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+static ALLOCS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator that forwards to the system allocator and counts allocations.
+#[cfg_attr(not(feature = "count-alloc"), allow(dead_code))]
+pub struct CountingAlloc;
+
+#[cfg_attr(not(feature = "count-alloc"), allow(dead_code))]
+fn record_alloc(size: usize) {
+    ALLOCS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    // NOTE: a reallocation counts as a new allocation of the new size, the old block is released.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether solutions were built with the counting allocator.
+pub const fn is_enabled() -> bool {
+    cfg!(feature = "count-alloc")
+}
+
+/// How solutions are run with respect to allocation counting.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AllocMode {
+    #[default]
+    Off,
+    /// Count the allocations of every part and print them next to its timing.
+    Count,
+    /// Count allocations and fail if a part allocates inside the benchmark loop after warm-up.
+    AssertNone,
+}
+
+impl AllocMode {
+    pub fn new(count: bool, assert_none: bool) -> Self {
+        match (count, assert_none) {
+            (_, true) => AllocMode::AssertNone,
+            (true, false) => AllocMode::Count,
+            (false, false) => AllocMode::Off,
+        }
+    }
+
+    /// Arguments for `cargo run` that build the solutions with the counting allocator.
+    pub fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            AllocMode::Off => &[],
+            AllocMode::Count | AllocMode::AssertNone => &["--features", "count-alloc"],
+        }
+    }
+
+    /// Arguments for the solution binary.
+    pub fn solution_args(self) -> &'static [&'static str] {
+        match self {
+            AllocMode::Off | AllocMode::Count => &[],
+            AllocMode::AssertNone => &["--assert-no-alloc"],
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Allocations made while running a function.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AllocStats {
+    pub allocs: u64,
+    pub bytes: u64,
+    /// Peak of the live bytes allocated by the function, on top of what was live before.
+    pub peak_bytes: u64,
+}
+
+/// Run a function and count the allocations it makes. Counts are only meaningful if [`is_enabled`].
+pub fn count<T>(func: impl FnOnce() -> T) -> (T, AllocStats) {
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+
+    let result = func();
+
+    let stats = AllocStats {
+        allocs: ALLOCS.load(Ordering::Relaxed) - allocs,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak_bytes: (PEAK.load(Ordering::Relaxed) - live) as u64,
+    };
+
+    (result, stats)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AllocStats, CountingAlloc, count};
+    use std::alloc::{GlobalAlloc, Layout};
+
+    #[test]
+    fn counts_allocations() {
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        // NOTE: the allocator is called directly, lib tests don't install it as the global allocator.
+        let ((), stats) = count(|| unsafe {
+            let a = CountingAlloc.alloc(layout);
+            let b = CountingAlloc.alloc_zeroed(layout);
+            CountingAlloc.dealloc(a, layout);
+            let b = CountingAlloc.realloc(b, layout, 128);
+            CountingAlloc.dealloc(b, Layout::from_size_align(128, 8).unwrap());
+        });
+
+        assert_eq!(
+            stats,
+            AllocStats {
+                allocs: 3,
+                bytes: 256,
+                peak_bytes: 128,
+            }
+        );
+    }
+}
@@ -0,0 +1,184 @@
+/// Store of the known answers of solved days, used to detect regressions with `cargo verify`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{self, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PuzzleId, Year, data_dir};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// The known answers of all days of a year, keyed by day and part.
+/// Can be serialized from / to JSON, e.g. `{ "01": { "1": "42", "2": "1337" } }`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    data: BTreeMap<Day, BTreeMap<u8, String>>,
+}
+
+impl Answers {
+    /// Dehydrate answers to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = fs::File::create(data_dir(year).join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from the JSON file of a year. If not present, returns an empty store.
+    /// Unlike timings, a malformed file is an error, since it would silently disable `verify` otherwise.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(data_dir(year).join(ANSWERS_FILE_NAME)) {
+            Ok(contents) => Answers::try_from(contents),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data.get(&day)?.get(&part).map(String::as_str)
+    }
+
+    /// Record the answer of a part, returning the previously recorded answer if there was one.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) -> Option<String> {
+        self.data
+            .entry(day)
+            .or_default()
+            .insert(part, answer.to_string())
+    }
+
+    /// All recorded answers of a day, ordered by part.
+    pub fn for_day(&self, day: Day) -> impl Iterator<Item = (u8, &str)> {
+        self.data
+            .get(&day)
+            .into_iter()
+            .flatten()
+            .map(|(part, answer)| (*part, answer.as_str()))
+    }
+
+    /// All days with at least one recorded answer.
+    pub fn days(&self) -> impl Iterator<Item = Day> + '_ {
+        self.data
+            .iter()
+            .filter(|(_, parts)| !parts.is_empty())
+            .map(|(day, _)| *day)
+    }
+}
+
+/// Record the answer of a part in the answers file of its year.
+pub fn record(puzzle: PuzzleId, part: u8, answer: &str) -> Result<Option<String>, String> {
+    let mut answers = Answers::read_from_file(puzzle.year)?;
+    let previous = answers.set(puzzle.day, part, answer);
+    answers.store_file(puzzle.year).map_err(|e| e.to_string())?;
+    Ok(previous)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let map: HashMap<String, JsonValue> = value
+            .data
+            .iter()
+            .map(|(day, parts)| {
+                let parts: HashMap<String, JsonValue> = parts
+                    .iter()
+                    .map(|(part, answer)| (part.to_string(), JsonValue::String(answer.clone())))
+                    .collect();
+                (day.to_string(), JsonValue::Object(parts))
+            })
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut data = BTreeMap::new();
+
+        for (day, parts) in days {
+            let day = Day::from_str(day).map_err(|_| format!("expected `{day}` to be a day."))?;
+
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("expected answers of day {day} to be an object."))?
+                .iter()
+                .map(|(part, answer)| {
+                    let part = part
+                        .parse::<u8>()
+                        .map_err(|_| format!("expected `{part}` of day {day} to be a part."))?;
+                    let answer = answer.get::<String>().ok_or(format!(
+                        "expected answer of day {day} part {part} to be a string."
+                    ))?;
+                    Ok((part, answer.clone()))
+                })
+                .collect::<Result<BTreeMap<_, _>, String>>()?;
+
+            data.insert(day, parts);
+        }
+
+        Ok(Answers { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+        answers.set(day!(1), 2, "1337");
+        answers.set(day!(8), 1, "#..#\n.##.");
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn reads_answers() {
+        let answers = Answers::try_from(
+            r#"{ "01": { "1": "42", "2": "1337" }, "08": { "1": "7" } }"#.to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(day!(1), 2), Some("1337"));
+        assert_eq!(answers.get(day!(8), 2), None);
+        assert_eq!(answers.days().collect::<Vec<_>>(), vec![day!(1), day!(8)]);
+        assert_eq!(
+            answers.for_day(day!(1)).collect::<Vec<_>>(),
+            vec![(1, "42"), (2, "1337")]
+        );
+    }
+
+    #[test]
+    fn overwrites_answers() {
+        let mut answers = Answers::default();
+        assert_eq!(answers.set(day!(1), 1, "42"), None);
+        assert_eq!(answers.set(day!(1), 1, "43"), Some("42".into()));
+        assert_eq!(answers.get(day!(1), 1), Some("43"));
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        assert!(Answers::try_from(r#"{ "01": { "1": 42 } }"#.to_string()).is_err());
+        assert!(Answers::try_from(r#"{ "day": { "1": "42" } }"#.to_string()).is_err());
+        assert!(Answers::try_from(r#"{ "01": { "one": "42" } }"#.to_string()).is_err());
+    }
+}
@@ -0,0 +1,544 @@
+/// Client for the Advent of Code website: downloads inputs, reads puzzles and submits answers.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+use ureq::Agent;
+
+use crate::template::{PuzzleId, data_dir};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+// NOTE: Advent of Code asks automated tools to identify themselves, `AOC_USER_AGENT` should name your repository or email.
+static DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    BadStatus(u16),
+    Request(ureq::Error),
+    Io(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or paste it into `~/{SESSION_FILE_NAME}`."
+            ),
+            AocClientError::BadStatus(400) => write!(
+                f,
+                "server responded with status 400, the session cookie is probably invalid or expired."
+            ),
+            AocClientError::BadStatus(404) => write!(
+                f,
+                "server responded with status 404, the puzzle is probably not unlocked yet."
+            ),
+            AocClientError::BadStatus(status) => {
+                write!(f, "server responded with status {status}.")
+            }
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::StatusCode(status) => AocClientError::BadStatus(status),
+            e => AocClientError::Request(e),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::Io(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, user_agent: &str) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(user_agent)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent,
+        }
+    }
+
+    /// Create a client from the environment.
+    /// The session cookie is read from `AOC_SESSION`, falling back to `~/.adventofcode.session`.
+    /// The base url defaults to the Advent of Code website and can be overridden with `AOC_BASE_URL`.
+    /// The user agent defaults to the name and version of this crate and can be overridden with `AOC_USER_AGENT`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(|| fs::read_to_string(session_file_path()?).ok())
+            .filter(|session| !session.trim().is_empty())
+            .ok_or(AocClientError::SessionNotFound)?;
+
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        let user_agent = env::var("AOC_USER_AGENT")
+            .ok()
+            .filter(|user_agent| !user_agent.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.into());
+
+        Ok(Self::new(&base_url, &session, &user_agent))
+    }
+
+    /// Fetch the personal input of a puzzle.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let mut response = self
+            .agent
+            .get(self.puzzle_url(puzzle, "/input"))
+            .header("Cookie", self.cookie())
+            .call()?;
+        Ok(response.body_mut().read_to_string()?)
+    }
+
+    /// Fetch the description of a puzzle as markdown. Includes part two once part one is solved.
+    pub fn description(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let mut response = self
+            .agent
+            .get(self.puzzle_url(puzzle, ""))
+            .header("Cookie", self.cookie())
+            .call()?;
+        let html = response.body_mut().read_to_string()?;
+        Ok(articles_to_markdown(&html))
+    }
+
+    /// Submit an answer, returns the response message of the website.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let mut response = self
+            .agent
+            .post(self.puzzle_url(puzzle, "/answer"))
+            .header("Cookie", self.cookie())
+            .send_form([("level", part.to_string()), ("answer", answer.to_string())])?;
+        let html = response.body_mut().read_to_string()?;
+        Ok(articles_to_markdown(&html))
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId, path: &str) -> String {
+        format!(
+            "{}/{}/day/{}{path}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn session_file_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(SESSION_FILE_NAME))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Download the input and the description of a puzzle to the data folder of its year.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    println!("Fetching puzzle {puzzle}...");
+    let input = client.input(puzzle)?;
+    let description = client.description(puzzle)?;
+
+    for folder in ["inputs", "puzzles"] {
+        fs::create_dir_all(data_dir(puzzle.year).join(folder))?;
+    }
+    fs::write(&input_path, input)?;
+    fs::write(&puzzle_path, description)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Print the description of a puzzle, refreshing the stored copy, e.g. after part two was unlocked.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let description = client.description(puzzle)?;
+
+    fs::create_dir_all(data_dir(puzzle.year).join("puzzles"))?;
+    fs::write(puzzle.data_path("puzzles", "md"), &description)?;
+
+    println!("{description}");
+    Ok(())
+}
+
+/// Submit an answer and print the response, which is returned to judge the verdict.
+pub fn submit(puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, AocClientError> {
+    let client = AocClient::from_env()?;
+    let response = client.submit(puzzle, part, answer)?;
+    println!("{response}");
+    Ok(response)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Convert the `<article>` elements of a page to markdown.
+/// Only supports the handful of elements used by puzzle descriptions and submission responses.
+fn articles_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let content_end = rest[content_start..]
+            .find("</article>")
+            .map_or(rest.len(), |i| content_start + i);
+
+        markdown.push_str(&html_to_markdown(&rest[content_start..content_end]));
+        rest = &rest[content_end..];
+    }
+
+    format!("{}\n", markdown.trim_end())
+}
+
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_end) = rest[tag_start..].find('>').map(|i| tag_start + i) else {
+            break;
+        };
+
+        let tag = &rest[tag_start + 1..tag_end];
+        rest = &rest[tag_end + 1..];
+
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match (name, closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p", true) => out.push_str("\n\n"),
+            ("ul", true) => out.push('\n'),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("a", false) => {
+                out.push('[');
+                links.push(attribute(tag, "href").unwrap_or_default());
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    out
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+
+    if in_pre {
+        out.push_str(&text);
+        return;
+    }
+
+    let text = text.replace('\n', " ");
+    if out.is_empty() || out.ends_with('\n') {
+        out.push_str(text.trim_start());
+    } else {
+        out.push_str(&text);
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(decode_entities(&tag[start..end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+
+        let replacement = entity.and_then(|(name, end)| {
+            let c = match name {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                _ => name
+                    .strip_prefix('#')
+                    .and_then(|code| match code.strip_prefix('x') {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => code.parse().ok(),
+                    })
+                    .and_then(char::from_u32)?,
+            };
+            Some((c, end))
+        });
+
+        match replacement {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        AocClient, AocClientError, DEFAULT_USER_AGENT, articles_to_markdown, decode_entities,
+    };
+    use crate::{day, template::PuzzleId, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serve one canned response per connection, returns the base url and a handle resolving to the received requests.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+
+                    let mut request = String::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                            content_length = length.trim().parse().unwrap();
+                        }
+                        request.push_str(&line);
+                        if line == "\r\n" {
+                            break;
+                        }
+                    }
+
+                    let mut body_buf = vec![0; content_length];
+                    reader.read_exact(&mut body_buf).unwrap();
+                    request.push_str(&String::from_utf8(body_buf).unwrap());
+
+                    write!(
+                        reader.get_mut(),
+                        "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+
+                    request
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+
+    fn puzzle() -> PuzzleId {
+        PuzzleId::new(year!(2025), day!(8)).unwrap()
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (base_url, server) = serve(vec![(200, "1 2 3\n")]);
+        let client = AocClient::new(&base_url, "secret\n", "aoc-test (me@example.com)");
+
+        assert_eq!(client.input(puzzle()).unwrap(), "1 2 3\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2025/day/8/input HTTP/1.1\r\n"));
+        assert!(
+            requests[0]
+                .to_lowercase()
+                .contains("cookie: session=secret\r\n")
+        );
+        assert!(
+            requests[0]
+                .to_lowercase()
+                .contains("user-agent: aoc-test (me@example.com)\r\n")
+        );
+    }
+
+    #[test]
+    fn fetches_description_as_markdown() {
+        let (base_url, server) = serve(vec![(
+            200,
+            "<html><main><article class=\"day-desc\"><h2>--- Day 8 ---</h2><p>Hello <em>world</em>.</p></article></main></html>",
+        )]);
+        let client = AocClient::new(&base_url, "secret", DEFAULT_USER_AGENT);
+
+        assert_eq!(
+            client.description(puzzle()).unwrap(),
+            "## --- Day 8 ---\n\nHello *world*.\n"
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2025/day/8 HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve(vec![(
+            200,
+            "<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article></main>",
+        )]);
+        let client = AocClient::new(&base_url, "secret", DEFAULT_USER_AGENT);
+
+        let response = client.submit(puzzle(), 2, "1337").unwrap();
+        assert_eq!(
+            response,
+            "That's the right answer!  You are one gold star closer.\n"
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2025/day/8/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("level=2&answer=1337"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, server) = serve(vec![(400, "Puzzle inputs differ by user.")]);
+        let client = AocClient::new(&base_url, "expired", DEFAULT_USER_AGENT);
+
+        assert!(matches!(
+            client.input(puzzle()),
+            Err(AocClientError::BadStatus(400))
+        ));
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn converts_puzzle_html() {
+        let html = r#"<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Read the <a href="/2025/about">about page</a>.</p>
+<p>For example:</p>
+<pre><code>L68
+<em>R48</em>
+</code></pre>
+<ul>
+<li>Use <code>x &lt; y</code>.</li>
+<li>Done.</li>
+</ul>
+</article><p>Answer: <code>42</code></p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>More.</p></article>"#;
+
+        assert_eq!(
+            articles_to_markdown(html),
+            [
+                "## --- Day 1: Test ---",
+                "",
+                "Read the [about page](/2025/about).",
+                "",
+                "For example:",
+                "",
+                "```",
+                "L68",
+                "R48",
+                "```",
+                "",
+                "- Use `x < y`.",
+                "- Done.",
+                "",
+                "## --- Part Two ---",
+                "",
+                "More.",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("&lt;a&gt; &amp;&amp; &quot;b&quot; &#39;c&#x27; & d"),
+            "<a> && \"b\" 'c' & d"
+        );
+    }
+}
@@ -1,5 +1,17 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{Year, all_days, allocations::AllocMode, run_multi::run_multi};
+
+pub fn handle(year: Year, is_release: bool) {
+    let run = run_multi(
+        year,
+        &all_days(year).collect(),
+        is_release,
+        false,
+        AllocMode::Off,
+    );
+
+    if !run.is_complete() {
+        process::exit(1);
+    }
 }
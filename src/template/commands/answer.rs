@@ -0,0 +1,16 @@
+use std::process;
+
+use crate::template::{PuzzleId, answers};
+
+pub fn handle(puzzle: PuzzleId, part: u8, answer: &str) {
+    match answers::record(puzzle, part, answer) {
+        Ok(Some(previous)) if previous != answer => {
+            println!("Updated answer of {puzzle} part {part}: {previous} → {answer}");
+        }
+        Ok(_) => println!("Recorded answer of {puzzle} part {part}: {answer}"),
+        Err(e) => {
+            eprintln!("Failed to record answer: {e}");
+            process::exit(1);
+        }
+    }
+}
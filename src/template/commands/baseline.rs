@@ -0,0 +1,189 @@
+use std::{
+    collections::HashSet,
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
+
+use crate::template::deltas::{self, PartDelta};
+use crate::template::history::{format_timestamp, git, unix_timestamp};
+use crate::template::machine::MachineProfile;
+use crate::template::run_multi::run_multi_in;
+use crate::template::stats::format_nanos;
+use crate::template::timings::Timings;
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Day, Year, all_days, allocations::AllocMode, data_dir,
+};
+
+/// Bench the solutions of a git revision and of the working tree with the same harness and print the change of every part.
+/// The revision is checked out into a temporary worktree, which is removed afterwards.
+pub fn handle(year: Year, day: Option<Day>, rev: &str, tolerance: f64, report: Option<&Path>) {
+    let days: HashSet<Day> =
+        day.map_or_else(|| all_days(year).collect(), |day| HashSet::from([day]));
+
+    let (label, baseline) = match bench_revision(year, &days, rev) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Failed to bench {rev}: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!();
+    println!("{ANSI_BOLD}Benching the working tree{ANSI_RESET}");
+    let run = run_multi_in(None, year, &days, true, true, AllocMode::Off);
+    let is_complete = run.is_complete();
+    let current = run.timings.unwrap_or_default();
+
+    let deltas = deltas::compare(&baseline, &current);
+
+    println!();
+    println!(
+        "{}",
+        deltas::format_table(&deltas, tolerance, &label, "Current")
+    );
+    println!();
+    println!(
+        "Total: {} → {}",
+        format_total(&baseline),
+        format_total(&current)
+    );
+
+    if let Some(path) = report {
+        let markdown = format_report(&label, &deltas, tolerance, &baseline, &current);
+        match fs::write(path, markdown) {
+            Ok(()) => println!("Wrote comparison report to {}.", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write {}: {e}", path.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    if !is_complete {
+        process::exit(1);
+    }
+}
+
+/// Bench `rev` in a temporary worktree. Returns the short commit hash of the revision and its timings.
+fn bench_revision(year: Year, days: &HashSet<Day>, rev: &str) -> Result<(String, Timings), String> {
+    let label = git(&["rev-parse", "--short", &format!("{rev}^{{commit}}")])
+        .ok_or_else(|| format!("`{rev}` is not a revision of this repository."))?;
+
+    let worktree = Worktree::add(&label)?;
+    worktree
+        .copy_inputs(year)
+        .map_err(|e| format!("failed to copy the inputs of {year}: {e}"))?;
+
+    println!("{ANSI_BOLD}Benching {label}{ANSI_RESET}");
+    let run = run_multi_in(Some(&worktree.path), year, days, true, true, AllocMode::Off);
+
+    // NOTE: revisions that predate reports of unsolved days have missing days, only a failed runner is an error.
+    if let Some(failure) = run.failure {
+        return Err(failure);
+    }
+
+    match run.timings {
+        Some(timings) if !timings.data.is_empty() => Ok((label, timings)),
+        _ => Err("the revision reported no timings, it may predate the runner.".into()),
+    }
+}
+
+/// A comparison report in markdown, e.g. to keep next to the notes of an optimisation.
+fn format_report(
+    label: &str,
+    deltas: &[PartDelta],
+    tolerance: f64,
+    baseline: &Timings,
+    current: &Timings,
+) -> String {
+    let machine = MachineProfile::detect();
+
+    let lines = [
+        format!("# Benchmark comparison: `{label}` vs working tree"),
+        String::new(),
+        format!(
+            "Benched on {} with `{}`, {} UTC. Changes beyond {tolerance}% are marked.",
+            machine.label(),
+            machine.rustc,
+            format_timestamp(unix_timestamp())
+        ),
+        String::new(),
+        deltas::format_markdown(deltas, tolerance, &format!("`{label}`"), "Working tree"),
+        String::new(),
+        format!(
+            "**Total: `{}` → `{}`**",
+            format_total(baseline),
+            format_total(current)
+        ),
+        String::new(),
+    ];
+
+    lines.join("\n")
+}
+
+fn format_total(timings: &Timings) -> String {
+    format_nanos(timings.total_millis() * 1_000_000.0)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A detached git worktree in the temp directory, removed when dropped.
+struct Worktree {
+    path: PathBuf,
+}
+
+impl Worktree {
+    fn add(rev: &str) -> Result<Self, String> {
+        let path = env::temp_dir().join(format!("aoc-baseline-{}", process::id()));
+
+        let status = Command::new("git")
+            .args(["worktree", "add", "--quiet", "--detach"])
+            .arg(&path)
+            .arg(rev)
+            .stdout(Stdio::null())
+            .status()
+            .map_err(|e| format!("failed to run git: {e}"))?;
+
+        if !status.success() {
+            return Err(format!(
+                "failed to check out {rev} into {}.",
+                path.display()
+            ));
+        }
+
+        Ok(Worktree { path })
+    }
+
+    /// Puzzle inputs are not committed, the worktree gets a copy of the inputs of the working tree.
+    fn copy_inputs(&self, year: Year) -> io::Result<()> {
+        let inputs = data_dir(year).join("inputs");
+        let target = self.path.join(&inputs);
+        fs::create_dir_all(&target)?;
+
+        for entry in fs::read_dir(&inputs)? {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                fs::copy(entry.path(), target.join(entry.file_name()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let removed = Command::new("git")
+            .args(["worktree", "remove", "--force"])
+            .arg(&self.path)
+            .status();
+
+        if !removed.is_ok_and(|status| status.success()) {
+            eprintln!(
+                "Failed to remove the worktree {}, run `git worktree prune` once it is deleted.",
+                self.path.display()
+            );
+        }
+    }
+}
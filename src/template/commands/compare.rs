@@ -0,0 +1,25 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::PuzzleId;
+
+pub fn handle(puzzle: PuzzleId, params: &[String]) {
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &puzzle.bin_name(),
+            "--",
+            "--compare",
+        ])
+        .args(params.iter().flat_map(|param| ["--param", param]))
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,111 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::PuzzleId;
+use crate::template::examples::{self, PartExample};
+
+pub fn handle(puzzle: PuzzleId, overwrite: bool) {
+    let Ok(markdown) = fs::read_to_string(puzzle.data_path("puzzles", "md")) else {
+        eprintln!(
+            "No puzzle description for {puzzle}. Download it first with `cargo download {} --year {}`.",
+            puzzle.day, puzzle.year
+        );
+        process::exit(1);
+    };
+
+    let parts = examples::extract(&markdown);
+
+    if parts[0].input.is_none() {
+        eprintln!("Found no example in the description of {puzzle}.");
+        process::exit(1);
+    }
+
+    let module_path = puzzle.module_path();
+    let mut module = fs::read_to_string(&module_path).ok();
+
+    for (part, example) in (1..).zip(&parts) {
+        // NOTE: the first example is shared by both parts, later ones are stored as `<day>-<part>.txt`.
+        let example_part = (part > 1 && example.input.is_some()).then_some(part);
+
+        if let Some(input) = &example.input {
+            write_example(example_path(puzzle, example_part), input, overwrite);
+        }
+
+        if let Some(module) = module.as_mut() {
+            fill_test(module, &module_path, part, example, example_part);
+        }
+    }
+
+    if let Some(module) = module
+        && let Err(e) = fs::write(&module_path, module)
+    {
+        eprintln!("Failed to write module file: {e}");
+        process::exit(1);
+    }
+}
+
+fn example_path(puzzle: PuzzleId, example_part: Option<u8>) -> PathBuf {
+    match example_part {
+        Some(part) => puzzle
+            .data_path("examples", "txt")
+            .with_file_name(format!("{}-{part}.txt", puzzle.day)),
+        None => puzzle.data_path("examples", "txt"),
+    }
+}
+
+fn write_example(path: PathBuf, input: &str, overwrite: bool) {
+    let is_empty = fs::read_to_string(&path).map_or(true, |contents| contents.trim().is_empty());
+
+    if !is_empty && !overwrite {
+        println!(
+            "Kept existing example file \"{}\", pass `--overwrite` to replace it.",
+            path.display()
+        );
+        return;
+    }
+
+    if let Some(parent) = path.parent()
+        && let Err(e) = fs::create_dir_all(parent)
+    {
+        eprintln!("Failed to create example folder: {e}");
+        process::exit(1);
+    }
+
+    match fs::write(&path, input) {
+        Ok(()) => println!("Wrote example file \"{}\"", path.display()),
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn fill_test(
+    module: &mut String,
+    module_path: &str,
+    part: u8,
+    example: &PartExample,
+    example_part: Option<u8>,
+) {
+    let Some(answer) = &example.answer else {
+        println!("Found no expected answer for part {part}.");
+        return;
+    };
+
+    // NOTE: scaffolded parts return `Option<u64>`, other answers have to be filled in by hand.
+    if answer.parse::<u64>().is_err() {
+        println!("Expected answer of part {part} is `{answer}`, fill it into the test by hand.");
+        return;
+    }
+
+    match examples::fill_test(module, part, answer, example_part) {
+        Some(filled) => {
+            *module = filled;
+            println!(
+                "Filled expected answer {answer} into the test of part {part} in \"{module_path}\""
+            );
+        }
+        None => println!(
+            "Expected answer of part {part} is {answer}, the test in \"{module_path}\" already has one."
+        ),
+    }
+}
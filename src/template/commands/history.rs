@@ -0,0 +1,91 @@
+use crate::template::history::{self, HistoryEntry, format_timestamp};
+use crate::template::machine::MachineProfile;
+use crate::template::stats::format_nanos;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, PuzzleId};
+
+pub fn handle(puzzle: PuzzleId) {
+    let machine = MachineProfile::detect().id();
+
+    // NOTE: timings of other machines are not comparable, entries without a machine predate machine tracking.
+    let entries: Vec<HistoryEntry> = history::read_from_file(puzzle.year)
+        .into_iter()
+        .filter(|entry| entry.day == puzzle.day)
+        .filter(|entry| entry.machine.as_ref().is_none_or(|id| *id == machine))
+        .collect();
+
+    if entries.is_empty() {
+        println!(
+            "No benchmark history for {puzzle} on this machine. Run `cargo time {} --year {}` to record some.",
+            puzzle.day, puzzle.year
+        );
+        return;
+    }
+
+    // NOTE: extra parts are grouped by name, so that reordering them doesn't mix up the history of different functions.
+    let mut parts: Vec<(u8, String)> = vec![];
+    for entry in &entries {
+        let label = entry.label();
+        if !parts.iter().any(|(_, part)| *part == label) {
+            parts.push((entry.part, label));
+        }
+    }
+    parts.sort_by_key(|(part, _)| *part);
+
+    println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+    println!("------");
+
+    for (_, label) in parts {
+        println!("{label}");
+
+        let part_entries: Vec<&HistoryEntry> = entries
+            .iter()
+            .filter(|entry| entry.label() == label)
+            .collect();
+
+        let mut previous: Option<u128> = None;
+
+        // NOTE: consecutive runs on the same revision are collapsed into the latest one.
+        for run in part_entries.chunk_by(|a, b| a.revision() == b.revision()) {
+            let entry = run.last().unwrap();
+
+            // NOTE: runs are compared including the setup, parts used to parse their own input.
+            let change = previous.map_or_else(String::new, |previous| {
+                format_change(previous, entry.total_nanos())
+            });
+
+            let runs = if run.len() > 1 {
+                format!(" {ANSI_ITALIC}({} runs){ANSI_RESET}", run.len())
+            } else {
+                String::new()
+            };
+
+            #[allow(clippy::cast_precision_loss)]
+            let duration = format_nanos(entry.nanos as f64);
+
+            #[allow(clippy::cast_precision_loss)]
+            let setup = entry.setup_nanos.map_or_else(String::new, |nanos| {
+                format!(
+                    " {ANSI_ITALIC}+ {} setup{ANSI_RESET}",
+                    format_nanos(nanos as f64)
+                )
+            });
+
+            println!(
+                "  {}  {:<8}  {duration:>9}  {change:>7}{setup}{runs}",
+                format_timestamp(entry.timestamp),
+                entry.revision(),
+            );
+
+            previous = Some(entry.total_nanos());
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_change(previous: u128, current: u128) -> String {
+    if previous == 0 {
+        return String::new();
+    }
+    let change = (current as f64 - previous as f64) / previous as f64 * 100.0;
+    format!("{change:+.1}%")
+}
@@ -1,6 +1,13 @@
 pub mod all;
+pub mod answer;
+pub mod baseline;
+pub mod compare;
 pub mod download;
+pub mod examples;
+pub mod history;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod verify;
+pub mod watch;
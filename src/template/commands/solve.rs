@@ -1,9 +1,26 @@
+use std::fs;
+use std::path::Path;
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::PuzzleId;
+use crate::template::allocations::AllocMode;
+use crate::template::heap::{self, format_bytes};
+use crate::template::machine::MachineProfile;
+use crate::template::timings::Timings;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    example: Option<&str>,
+    input: Option<&str>,
+    params: &[String],
+    count_alloc: bool,
+    trace: Option<&Path>,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -16,6 +33,15 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if count_alloc {
+        cmd_args.extend(
+            AllocMode::Count
+                .cargo_args()
+                .iter()
+                .map(ToString::to_string),
+        );
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +49,33 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(example) = example {
+        cmd_args.push("--example".to_string());
+        cmd_args.push(example.to_string());
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.to_string());
+    }
+
+    for param in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.clone());
+    }
+
+    if let Some(trace) = trace {
+        cmd_args.push("--trace".to_string());
+        cmd_args.push(trace.display().to_string());
+    }
+
+    if dhat {
+        // NOTE: remove profiles of earlier runs, so a part that fails to run is not recorded with stale data.
+        for part in heap::dhat_parts() {
+            let _ = fs::remove_file(heap::dhat_file_name(part));
+        }
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -31,4 +84,40 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .unwrap();
 
     cmd.wait().unwrap();
+
+    if dhat {
+        record_heap_profile(puzzle);
+    }
+}
+
+/// Record the heap profile of a `--dhat` run in the timings of the year.
+/// The readme is left alone, the profile shows up in its benchmark table with the next `cargo time --store`.
+fn record_heap_profile(puzzle: PuzzleId) {
+    let profile = match heap::read_dhat_profiles(&heap::dhat_parts()) {
+        Ok(Some(profile)) => profile,
+        Ok(None) => {
+            eprintln!("No heap profile was written, not recording it.");
+            return;
+        }
+        Err(e) => {
+            eprintln!("Failed to read heap profile: {e}");
+            return;
+        }
+    };
+
+    let machine = MachineProfile::detect();
+    let mut timings = Timings::read_from_file(puzzle.year, &machine);
+    timings.set_heap(puzzle.day, profile);
+
+    if let Err(e) = timings.store_file(puzzle.year, &machine) {
+        eprintln!("Failed to store heap profile: {e}");
+        return;
+    }
+
+    println!(
+        "Recorded heap profile: {} in {} allocations, peak {}.",
+        format_bytes(profile.total_bytes),
+        profile.total_blocks,
+        format_bytes(profile.peak_bytes)
+    );
 }
@@ -1,40 +1,155 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
+use crate::template::allocations::AllocMode;
+use crate::template::deltas;
+use crate::template::history::{self, BuildInfo, HistoryEntry};
+use crate::template::machine::MachineProfile;
+use crate::template::protocol::PartReport;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Day, PuzzleId, Year, all_days, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    count_alloc: bool,
+    assert_no_alloc: bool,
+    check: Option<f64>,
+) {
+    let alloc = AllocMode::new(count_alloc, assert_no_alloc);
+    let machine = MachineProfile::detect();
+    let stored_timings = Timings::read_from_file(year, &machine);
+
+    if check.is_some() && stored_timings.data.is_empty() {
+        eprintln!(
+            "No timings stored for {year} on this machine ({}), run `cargo time --store` first.",
+            machine.id()
+        );
+        process::exit(1);
+    }
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_days(year).collect()
+            } else if check.is_some() {
+                // a check only benches the days it can compare against.
+                stored_timings
+                    .data
+                    .iter()
+                    .map(|timing| timing.day)
+                    .collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                all_days(year)
+                    .filter(|day| {
+                        PuzzleId::new(year, *day)
+                            .is_some_and(|puzzle| !stored_timings.is_day_complete(puzzle))
+                    })
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run = run_multi(year, &days_to_run, true, true, alloc);
+
+    // NOTE: parts that allocated still have valid timings, they are recorded before the run fails.
+    let violations: Vec<&PartReport> = run
+        .reports
+        .iter()
+        .filter(|report| report.is_alloc_violation())
+        .collect();
+
+    let is_complete = run.is_complete();
+    let timings = run.timings.unwrap();
+
+    let entries = HistoryEntry::from_reports(&run.reports, &BuildInfo::detect());
+    if let Err(e) = history::append(year, &entries) {
+        eprintln!("Failed to append to benchmark history: {e}");
+    }
+
+    if let Some(tolerance) = check {
+        check_timings(&stored_timings, &timings, &days_to_run, tolerance);
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year, &machine).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, &Timings::read_all(year)) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
+
+    if !violations.is_empty() {
+        print_alloc_violations(&violations);
+    }
+
+    // NOTE: the days that did report are stored, a crash of one day doesn't throw away the timings of the others.
+    if !violations.is_empty() || !is_complete {
+        process::exit(1);
+    }
+}
+
+/// List the parts that allocated inside the benchmark loop with `--assert-no-alloc`.
+fn print_alloc_violations(violations: &[&PartReport]) {
+    eprintln!();
+    eprintln!(
+        "✖ {} part(s) allocated inside the benchmark loop after warm-up:",
+        violations.len()
+    );
+
+    for report in violations {
+        eprintln!(
+            "  Day {}, {}: {} allocations",
+            report.puzzle.day,
+            report.label().unwrap_or_default(),
+            report.bench_allocs.unwrap_or_default()
+        );
+    }
+}
+
+/// Compare a run to the stored timings and exit with an error if a part regressed beyond `tolerance` percent.
+/// Requested days with stored timings that are missing from the run fail the check.
+fn check_timings(stored: &Timings, current: &Timings, days: &HashSet<Day>, tolerance: f64) {
+    let stored = Timings {
+        data: stored
+            .data
+            .iter()
+            .filter(|timing| days.contains(&timing.day))
+            .cloned()
+            .collect(),
+    };
+
+    let deltas = deltas::compare(&stored, current);
+
+    println!();
+    println!(
+        "{}",
+        deltas::format_table(&deltas, tolerance, "Stored", "Current")
+    );
+    println!();
+
+    let failures = deltas
+        .iter()
+        .filter(|delta| delta.is_failure(tolerance))
+        .count();
+
+    if failures > 0 {
+        eprintln!(
+            "✖ {failures} part(s) regressed beyond the tolerance of {tolerance}% or are missing."
+        );
+        process::exit(1);
+    }
+
+    println!("✔ No regressions beyond the tolerance of {tolerance}%.");
 }
@@ -0,0 +1,99 @@
+use std::{collections::HashSet, process};
+
+use crate::template::allocations::AllocMode;
+use crate::template::answers::Answers;
+use crate::template::protocol::PartReport;
+use crate::template::run_multi::run_multi;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Year};
+
+pub fn handle(year: Year, day: Option<Day>) {
+    let answers = match Answers::read_from_file(year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to read data/{year}/answers.json: {e}");
+            process::exit(1);
+        }
+    };
+
+    let days_to_verify: HashSet<Day> = match day {
+        Some(day) => HashSet::from([day]),
+        None => answers.days().collect(),
+    };
+
+    if days_to_verify.is_empty() {
+        println!(
+            "No known answers of {year} to verify. Answers are recorded by `cargo solve <day> --submit <part>` or `cargo answer <day> <part> <answer>`."
+        );
+        return;
+    }
+
+    if let Some(day) = day
+        && answers.for_day(day).next().is_none()
+    {
+        eprintln!(
+            "No known answers for day {day} of {year}. Record them with `cargo answer {day} <part> <answer> --year {year}`."
+        );
+        process::exit(1);
+    }
+
+    let run = run_multi(year, &days_to_verify, true, false, AllocMode::Off);
+
+    let mut days: Vec<Day> = days_to_verify.into_iter().collect();
+    days.sort_unstable();
+
+    let mut checked = 0;
+    let mut failures = 0;
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------------");
+
+    for day in days {
+        for (part, expected) in answers.for_day(day) {
+            checked += 1;
+
+            if let Some(failure) = check_part(&run.reports, day, part, expected) {
+                failures += 1;
+                println!("Day {day} part {part}: ✖ {failure}");
+            }
+        }
+    }
+
+    if failures == 0 {
+        println!("All known answers match ({checked} checked).");
+    } else {
+        println!("{failures} of {checked} known answers did not match.");
+    }
+
+    if failures > 0 || !run.is_complete() {
+        process::exit(1);
+    }
+}
+
+/// Compare the answer a part reported with the known answer, returning a description of the difference if they don't match.
+fn check_part(reports: &[PartReport], day: Day, part: u8, expected: &str) -> Option<String> {
+    let report = reports
+        .iter()
+        .find(|report| report.puzzle.day == day && report.part == Some(part));
+
+    let Some(report) = report else {
+        let error = reports
+            .iter()
+            .find(|report| report.puzzle.day == day)
+            .and_then(|report| report.error.clone());
+        return Some(error.unwrap_or_else(|| "did not run".into()));
+    };
+
+    match &report.answer {
+        Some(actual) if actual == expected => None,
+        Some(actual) => Some(format!(
+            "answer changed\n  - {}\n  + {}",
+            expected.replace('\n', "\n    "),
+            actual.replace('\n', "\n    ")
+        )),
+        None => Some(format!(
+            "no answer\n  - {}",
+            expected.replace('\n', "\n    ")
+        )),
+    }
+}
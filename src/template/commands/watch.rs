@@ -0,0 +1,488 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
+
+use crate::template::protocol::PartReport;
+use crate::template::stats::format_nanos;
+use crate::template::{ANSI_BOLD, ANSI_RESET, PuzzleId, data_dir};
+
+/// Rebuild a day on every change to its module, the library, its input or its examples.
+/// Each cycle runs the tests of the day, then the solution against the real input, and prints how the answers changed.
+pub fn handle(puzzle: PuzzleId, release: bool, params: &[String]) {
+    let watched = watched_files(puzzle);
+    let mut watcher = Watcher::new(&watched).unwrap_or_else(|e| {
+        eprintln!("Failed to watch the files of {puzzle}: {e}");
+        process::exit(1);
+    });
+
+    println!(
+        "Watching {} for changes, press Ctrl+C to stop.",
+        watched
+            .iter()
+            .map(|file| file.path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let mut previous: Option<BTreeMap<u8, Option<String>>> = None;
+
+    loop {
+        println!();
+        println!("{ANSI_BOLD}{puzzle}{ANSI_RESET}");
+
+        if let Some(answers) = run_cycle(puzzle, release, params, previous.as_ref()) {
+            previous = Some(answers);
+        }
+
+        match watcher.wait() {
+            Ok(changed) => println!("\nChanged: {}", changed.display()),
+            Err(e) => {
+                eprintln!("Failed to watch the files of {puzzle}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Build, test and run the day once. Returns the answers of the run, `None` if the solution could not run.
+fn run_cycle(
+    puzzle: PuzzleId,
+    release: bool,
+    params: &[String],
+    previous: Option<&BTreeMap<u8, Option<String>>>,
+) -> Option<BTreeMap<u8, Option<String>>> {
+    let profile: &[&str] = if release { &["--release"] } else { &[] };
+
+    let build = Command::new("cargo")
+        .args(["build", "--quiet", "--bin", &puzzle.bin_name()])
+        .args(profile)
+        .status();
+
+    if !build.is_ok_and(|status| status.success()) {
+        println!("Build: ✖ failed");
+        return None;
+    }
+
+    match Command::new("cargo")
+        .args(["test", "--bin", &puzzle.bin_name()])
+        .args(profile)
+        .stdin(Stdio::null())
+        .output()
+    {
+        Ok(output) => {
+            let summary = TestSummary::parse(&String::from_utf8_lossy(&output.stdout));
+            if summary.passed == 0 && summary.failed.is_empty() && !output.status.success() {
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            }
+            println!("Tests: {summary}");
+        }
+        Err(e) => println!("Tests: ✖ could not run: {e}"),
+    }
+
+    let output = match Command::new("cargo")
+        .args(["run", "--quiet", "--bin", &puzzle.bin_name()])
+        .args(profile)
+        .args(["--", "--json"])
+        .args(params.iter().flat_map(|param| ["--param", param]))
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            println!("Solution: ✖ could not run: {e}");
+            return None;
+        }
+    };
+
+    let reports: Vec<PartReport> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(PartReport::from_json_line)
+        .collect();
+
+    if reports.iter().all(|report| report.part.is_none()) {
+        match reports.iter().find_map(|report| report.error.as_deref()) {
+            Some(error) => println!("Solution: ✖ {error}"),
+            None => println!("Solution: ✖ failed"),
+        }
+        return None;
+    }
+
+    let mut answers = BTreeMap::new();
+
+    for report in &reports {
+        let Some(part) = report.part else {
+            continue;
+        };
+
+        let before = previous.map(|previous| previous.get(&part).cloned().flatten());
+        println!(
+            "{}: {} ({})",
+            report.label().unwrap_or_default(),
+            describe_answer(
+                before.as_ref().map(Option::as_deref),
+                report.answer.as_deref()
+            ),
+            format_nanos(report.nanos as f64)
+        );
+
+        answers.insert(part, report.answer.clone());
+    }
+
+    Some(answers)
+}
+
+/// Describe the answer of a part compared to the previous run, `None` if there is no previous run.
+fn describe_answer(previous: Option<Option<&str>>, current: Option<&str>) -> String {
+    let current_str = current.unwrap_or("✖");
+
+    match previous {
+        None => current_str.to_string(),
+        Some(previous) if previous == current => format!("{current_str}, unchanged"),
+        Some(previous) => {
+            let previous = previous.unwrap_or("✖");
+            if previous.contains('\n') || current_str.contains('\n') {
+                format!(
+                    "changed\n  - {}\n  + {}",
+                    previous.replace('\n', "\n    "),
+                    current_str.replace('\n', "\n    ")
+                )
+            } else {
+                format!("{current_str}, was {previous}")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of the tests of a day, parsed from the output of the test harness.
+#[derive(Debug, Default, PartialEq)]
+struct TestSummary {
+    passed: usize,
+    failed: Vec<String>,
+}
+
+impl TestSummary {
+    fn parse(output: &str) -> Self {
+        let mut summary = TestSummary::default();
+
+        for line in output.lines() {
+            let Some((name, outcome)) = line
+                .strip_prefix("test ")
+                .and_then(|line| line.rsplit_once(" ... "))
+            else {
+                continue;
+            };
+
+            match outcome.trim() {
+                "ok" => summary.passed += 1,
+                "FAILED" => summary.failed.push(name.to_string()),
+                _ => {}
+            }
+        }
+
+        summary
+    }
+}
+
+impl std::fmt::Display for TestSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.passed, self.failed.len()) {
+            (0, 0) => write!(f, "no tests"),
+            (passed, 0) => write!(f, "✔ {passed} passed"),
+            (passed, failed) => write!(
+                f,
+                "✖ {failed} of {} failed: {}",
+                passed + failed,
+                self.failed.join(", ")
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A file that triggers a new cycle when it changes.
+struct WatchedFile {
+    path: PathBuf,
+    /// Whether the file name is a prefix, e.g. all examples of a day.
+    is_prefix: bool,
+}
+
+impl WatchedFile {
+    fn matches(&self, path: &Path) -> bool {
+        if !self.is_prefix {
+            return path == self.path;
+        }
+
+        let (Some(name), Some(prefix)) = (path.file_name(), self.path.file_name()) else {
+            return false;
+        };
+
+        path.parent() == self.path.parent()
+            && name
+                .to_string_lossy()
+                .starts_with(&*prefix.to_string_lossy())
+    }
+}
+
+fn watched_files(puzzle: PuzzleId) -> Vec<WatchedFile> {
+    vec![
+        WatchedFile {
+            path: puzzle.module_path().into(),
+            is_prefix: false,
+        },
+        WatchedFile {
+            path: "src/lib.rs".into(),
+            is_prefix: false,
+        },
+        WatchedFile {
+            path: puzzle.data_path("inputs", "txt"),
+            is_prefix: false,
+        },
+        WatchedFile {
+            path: data_dir(puzzle.year)
+                .join("examples")
+                .join(puzzle.day.to_string()),
+            is_prefix: true,
+        },
+    ]
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(target_os = "linux")]
+use linux::Watcher;
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{
+        collections::HashMap,
+        fs,
+        io::{self, ErrorKind},
+        mem,
+        path::{Path, PathBuf},
+        thread,
+        time::Duration,
+    };
+
+    use inotify::{Inotify, WatchDescriptor, WatchMask};
+
+    use super::WatchedFile;
+
+    /// Waits for changes of the watched files with inotify.
+    /// Watches the folders instead of the files, since editors often save by replacing the file.
+    pub struct Watcher<'a> {
+        inotify: Inotify,
+        folders: HashMap<WatchDescriptor, PathBuf>,
+        /// Folders of watched files that don't exist yet, e.g. the inputs before the first download.
+        /// Their nearest existing parent is watched until they are created.
+        missing: Vec<PathBuf>,
+        files: &'a [WatchedFile],
+    }
+
+    impl<'a> Watcher<'a> {
+        pub fn new(files: &'a [WatchedFile]) -> io::Result<Self> {
+            let mut missing: Vec<PathBuf> = vec![];
+
+            for file in files {
+                let folder = file.path.parent().map(PathBuf::from).unwrap_or_default();
+                if !missing.contains(&folder) {
+                    missing.push(folder);
+                }
+            }
+
+            let mut watcher = Watcher {
+                inotify: Inotify::init()?,
+                folders: HashMap::new(),
+                missing,
+                files,
+            };
+
+            watcher.watch_folders()?;
+            Ok(watcher)
+        }
+
+        /// Watch the folders that are missing so far, or their nearest existing parent while they don't exist.
+        /// Returns a watched file that is already present in a folder that was just created.
+        fn watch_folders(&mut self) -> io::Result<Option<PathBuf>> {
+            let mut present = None;
+
+            for folder in mem::take(&mut self.missing) {
+                if folder.as_os_str().is_empty() || folder.is_dir() {
+                    self.watch(&folder)?;
+                    present = present.or_else(|| self.find_file(&folder));
+                    continue;
+                }
+
+                let parent = folder
+                    .ancestors()
+                    .skip(1)
+                    .find(|parent| parent.as_os_str().is_empty() || parent.is_dir())
+                    .unwrap_or(Path::new(""));
+
+                self.watch(parent)?;
+                self.missing.push(folder);
+            }
+
+            Ok(present)
+        }
+
+        fn watch(&mut self, folder: &Path) -> io::Result<()> {
+            if self.folders.values().any(|watched| watched == folder) {
+                return Ok(());
+            }
+
+            // NOTE: the current directory is recorded as an empty path, so that joined paths stay relative.
+            let target = if folder.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                folder
+            };
+
+            let wd = self.inotify.watches().add(
+                target,
+                WatchMask::CLOSE_WRITE
+                    | WatchMask::MOVED_TO
+                    | WatchMask::CREATE
+                    | WatchMask::DELETE,
+            )?;
+
+            self.folders.insert(wd, folder.to_path_buf());
+            Ok(())
+        }
+
+        fn find_file(&self, folder: &Path) -> Option<PathBuf> {
+            fs::read_dir(folder)
+                .ok()?
+                .filter_map(|entry| Some(folder.join(entry.ok()?.file_name())))
+                .find(|path| self.files.iter().any(|file| file.matches(path)))
+        }
+
+        /// Block until a watched file changes, returns its path.
+        pub fn wait(&mut self) -> io::Result<PathBuf> {
+            let mut buffer = [0; 4096];
+
+            loop {
+                let paths: Vec<PathBuf> = self
+                    .inotify
+                    .read_events_blocking(&mut buffer)?
+                    .filter_map(|event| Some(self.folders.get(&event.wd)?.join(event.name?)))
+                    .collect();
+
+                let mut changed = paths
+                    .iter()
+                    .find(|path| self.files.iter().any(|file| file.matches(path)))
+                    .cloned();
+
+                // a folder on the way to a missing one was created, watch it and pick up files created along with it.
+                if paths
+                    .iter()
+                    .any(|path| self.missing.iter().any(|folder| folder.starts_with(path)))
+                {
+                    let present = self.watch_folders()?;
+                    changed = changed.or(present);
+                }
+
+                if let Some(path) = changed {
+                    // NOTE: saving a file often emits several events, let them settle before rebuilding.
+                    thread::sleep(Duration::from_millis(100));
+                    loop {
+                        match self.inotify.read_events(&mut buffer) {
+                            Ok(_) => {}
+                            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                            Err(e) => return Err(e),
+                        }
+                    }
+                    return Ok(path);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+use fallback::Watcher;
+
+#[cfg(not(target_os = "linux"))]
+mod fallback {
+    use std::{io, path::PathBuf};
+
+    use super::WatchedFile;
+
+    pub struct Watcher;
+
+    impl Watcher {
+        pub fn new(_: &[WatchedFile]) -> io::Result<Self> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "`--watch` is built on inotify and only supported on Linux",
+            ))
+        }
+
+        pub fn wait(&mut self) -> io::Result<PathBuf> {
+            unreachable!()
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{TestSummary, WatchedFile, describe_answer};
+
+    #[test]
+    fn summarizes_tests() {
+        let summary = TestSummary::parse(
+            "running 3 tests\ntest tests::test_part_one ... ok\ntest example_cases::example_default_part_2 ... FAILED\ntest tests::test_part_two ... ignored\n\ntest result: FAILED.",
+        );
+
+        assert_eq!(summary.passed, 1);
+        assert_eq!(
+            summary.failed,
+            vec!["example_cases::example_default_part_2".to_string()]
+        );
+        assert_eq!(
+            summary.to_string(),
+            "✖ 1 of 2 failed: example_cases::example_default_part_2"
+        );
+        assert_eq!(TestSummary::default().to_string(), "no tests");
+    }
+
+    #[test]
+    fn describes_answer_changes() {
+        assert_eq!(describe_answer(None, Some("42")), "42");
+        assert_eq!(
+            describe_answer(Some(Some("42")), Some("42")),
+            "42, unchanged"
+        );
+        assert_eq!(describe_answer(Some(Some("41")), Some("42")), "42, was 41");
+        assert_eq!(describe_answer(Some(None), Some("42")), "42, was ✖");
+        assert_eq!(
+            describe_answer(Some(Some("#.\n.#")), Some("##\n.#")),
+            "changed\n  - #.\n    .#\n  + ##\n    .#"
+        );
+    }
+
+    #[test]
+    fn matches_watched_files() {
+        let examples = WatchedFile {
+            path: "data/2025/examples/08".into(),
+            is_prefix: true,
+        };
+        assert!(examples.matches("data/2025/examples/08.txt".as_ref()));
+        assert!(examples.matches("data/2025/examples/08.json".as_ref()));
+        assert!(!examples.matches("data/2025/examples/11.txt".as_ref()));
+
+        let module = WatchedFile {
+            path: "src/bin/2025_08.rs".into(),
+            is_prefix: false,
+        };
+        assert!(module.matches("src/bin/2025_08.rs".as_ref()));
+        assert!(!module.matches("src/bin/2025_09.rs".as_ref()));
+    }
+}
@@ -0,0 +1,319 @@
+/// Parameters passed to solution parts, for puzzles where e.g. a count differs between the example and the real input.
+use std::{
+    any::type_name,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    process,
+    str::FromStr,
+};
+
+/// Named parameters of a run.
+/// Declared for the real input with `params = { .. }` in [`solution!`](crate::solution), per example case in the
+/// examples manifest, and overridden on the command line with `--param <name>=<value>`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Context {
+    params: BTreeMap<String, String>,
+    /// Parameters whose value was set with `--param`, so a value that can't be parsed is reported as a CLI error.
+    overridden: BTreeSet<String>,
+}
+
+impl Context {
+    /// Set a parameter, replacing its previous value.
+    #[must_use]
+    pub fn with(mut self, name: &str, value: impl Display) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self.overridden.remove(name);
+        self
+    }
+
+    /// Set a parameter from a `--param <name>=<value>` argument, replacing its previous value.
+    #[must_use]
+    pub fn with_override(mut self, name: &str, value: impl Display) -> Self {
+        self = self.with(name, value);
+        self.overridden.insert(name.to_string());
+        self
+    }
+
+    /// Parse the value of a parameter.
+    /// Panics if a declared value can't be parsed, since that is a mistake in the declaration. A value passed with
+    /// `--param` that can't be parsed is a mistake on the command line instead, it is reported and the run is aborted.
+    pub fn param<T: FromStr>(&self, name: &str) -> Option<T> {
+        let value = self.params.get(name)?;
+        match value.parse() {
+            Ok(value) => Some(value),
+            Err(_) if self.overridden.contains(name) => {
+                eprintln!(
+                    "Invalid argument `--param {name}={value}`: expected a value of type `{}`.",
+                    type_name::<T>()
+                );
+                process::exit(1);
+            }
+            Err(_) => panic!("parameter `{name}` has the invalid value `{value}`."),
+        }
+    }
+
+    /// Apply the parameters of `other` on top of these.
+    #[must_use]
+    pub fn merge(mut self, other: &Context) -> Self {
+        for (name, value) in &other.params {
+            self = if other.overridden.contains(name) {
+                self.with_override(name, value)
+            } else {
+                self.with(name, value)
+            };
+        }
+        self
+    }
+}
+
+/// Parse a parameter override of the form `<name>=<value>`, e.g. `edges=1000`.
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!(
+            "expected a parameter as <name>=<value>, e.g. edges=1000, got `{s}`."
+        )),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// What a solution part returns: `Option<T>`, or `Result<T, E>` to report why a part failed.
+pub trait PartOutput {
+    type Answer;
+
+    /// The answer of the part, `Ok(None)` if it has none. Errors are formatted with `{:#}`, which prints the whole
+    /// chain of causes for error types that support it, such as [`ParseError`](crate::template::ParseError).
+    fn into_answer(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T> PartOutput for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T, E: Display> PartOutput for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        self.map(Some).map_err(|e| format!("{e:#}"))
+    }
+}
+
+/// A solution part. Implemented for functions that take the input, and for functions that also take the [`Context`]:
+///
+/// ```ignore
+/// pub fn part_one(input: &str) -> Option<u64> { .. }
+/// pub fn part_one(input: &str, ctx: &Context) -> Result<u64, ParseError> { .. }
+/// ```
+///
+/// `Signature` only distinguishes the two kinds of functions, it is inferred.
+pub trait PartFn<Signature> {
+    type Answer;
+
+    fn call(&self, input: &str, ctx: &Context) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<F, R> PartFn<fn(&str) -> R> for F
+where
+    F: Fn(&str) -> R,
+    R: PartOutput,
+{
+    type Answer = R::Answer;
+
+    fn call(&self, input: &str, _: &Context) -> Result<Option<R::Answer>, String> {
+        self(input).into_answer()
+    }
+}
+
+impl<F, R> PartFn<fn(&str, &Context) -> R> for F
+where
+    F: Fn(&str, &Context) -> R,
+    R: PartOutput,
+{
+    type Answer = R::Answer;
+
+    fn call(&self, input: &str, ctx: &Context) -> Result<Option<R::Answer>, String> {
+        self(input, ctx).into_answer()
+    }
+}
+
+/// Prepares the input of a day that is set up per execution, see `setup` in [`solution!`](crate::solution).
+/// Implemented for functions that take the input, and for functions that also take the [`Context`].
+pub trait SetupFn<Signature> {
+    type Input;
+
+    fn setup(&self, input: &str, ctx: &Context) -> Self::Input;
+}
+
+impl<F, I> SetupFn<fn(&str) -> I> for F
+where
+    F: Fn(&str) -> I,
+{
+    type Input = I;
+
+    fn setup(&self, input: &str, _: &Context) -> I {
+        self(input)
+    }
+}
+
+impl<F, I> SetupFn<fn(&str, &Context) -> I> for F
+where
+    F: Fn(&str, &Context) -> I,
+{
+    type Input = I;
+
+    fn setup(&self, input: &str, ctx: &Context) -> I {
+        self(input, ctx)
+    }
+}
+
+/// A solution part that takes the input produced by a [`SetupFn`] by value, and may consume or mutate it:
+///
+/// ```ignore
+/// pub fn part_two(mut grid: Grid) -> Option<u64> { .. }
+/// pub fn part_two(mut grid: Grid, ctx: &Context) -> Result<u64, ParseError> { .. }
+/// ```
+pub trait SetupPartFn<Input, Signature> {
+    type Answer;
+
+    fn call_with(&self, input: Input, ctx: &Context) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<F, I, R> SetupPartFn<I, fn(I) -> R> for F
+where
+    F: Fn(I) -> R,
+    R: PartOutput,
+{
+    type Answer = R::Answer;
+
+    fn call_with(&self, input: I, _: &Context) -> Result<Option<R::Answer>, String> {
+        self(input).into_answer()
+    }
+}
+
+impl<F, I, R> SetupPartFn<I, fn(I, &Context) -> R> for F
+where
+    F: Fn(I, &Context) -> R,
+    R: PartOutput,
+{
+    type Answer = R::Answer;
+
+    fn call_with(&self, input: I, ctx: &Context) -> Result<Option<R::Answer>, String> {
+        self(input, ctx).into_answer()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Context, PartFn, SetupFn, SetupPartFn, parse_param};
+
+    fn without_context(input: &str) -> Option<usize> {
+        Some(input.len())
+    }
+
+    fn with_context(input: &str, ctx: &Context) -> Option<usize> {
+        Some(input.len() * ctx.param::<usize>("factor")?)
+    }
+
+    fn fallible(input: &str) -> Result<u32, String> {
+        input
+            .parse()
+            .map_err(|_| format!("`{input}` is not a number"))
+    }
+
+    #[test]
+    fn calls_both_kinds_of_parts() {
+        let ctx = Context::default().with("factor", 3);
+        assert_eq!(without_context.call("abc", &ctx), Ok(Some(3)));
+        assert_eq!(with_context.call("abc", &ctx), Ok(Some(9)));
+        assert_eq!(with_context.call("abc", &Context::default()), Ok(None));
+    }
+
+    #[test]
+    fn calls_fallible_parts() {
+        let ctx = Context::default();
+        assert_eq!(fallible.call("42", &ctx), Ok(Some(42)));
+        assert_eq!(
+            fallible.call("4x2", &ctx),
+            Err("`4x2` is not a number".into())
+        );
+    }
+
+    #[test]
+    fn calls_parts_with_setup() {
+        fn digits(input: &str) -> Vec<u32> {
+            input.chars().filter_map(|c| c.to_digit(10)).collect()
+        }
+
+        fn scaled(input: &str, ctx: &Context) -> Vec<u32> {
+            let factor = ctx.param("factor").unwrap_or(1);
+            digits(input).into_iter().map(|d| d * factor).collect()
+        }
+
+        fn largest(mut digits: Vec<u32>) -> Option<u32> {
+            digits.sort_unstable();
+            digits.pop()
+        }
+
+        let ctx = Context::default().with("factor", 10);
+        assert_eq!(
+            largest.call_with(digits.setup("3a81", &ctx), &ctx),
+            Ok(Some(8))
+        );
+        assert_eq!(
+            largest.call_with(scaled.setup("3a81", &ctx), &ctx),
+            Ok(Some(80))
+        );
+        assert_eq!(largest.call_with(digits.setup("", &ctx), &ctx), Ok(None));
+    }
+
+    #[test]
+    fn merges_params() {
+        let ctx = Context::default()
+            .with("edges", 1000)
+            .with("size", 71)
+            .merge(&Context::default().with("edges", 10));
+
+        assert_eq!(ctx.param::<usize>("edges"), Some(10));
+        assert_eq!(ctx.param::<usize>("size"), Some(71));
+        assert_eq!(ctx.param::<usize>("missing"), None);
+    }
+
+    #[test]
+    fn keeps_track_of_overrides() {
+        let ctx = Context::default()
+            .with("edges", 1000)
+            .merge(&Context::default().with_override("edges", 10));
+        assert!(ctx.overridden.contains("edges"));
+        assert_eq!(ctx.param::<usize>("edges"), Some(10));
+
+        let ctx = ctx.merge(&Context::default().with("edges", 20));
+        assert!(!ctx.overridden.contains("edges"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_invalid_values() {
+        Context::default()
+            .with("edges", "many")
+            .param::<usize>("edges");
+    }
+
+    #[test]
+    fn parses_overrides() {
+        assert_eq!(
+            parse_param("edges=1000"),
+            Ok(("edges".to_string(), "1000".to_string()))
+        );
+        assert!(parse_param("edges").is_err());
+        assert!(parse_param("=1000").is_err());
+    }
+}
@@ -0,0 +1,339 @@
+/// Per-part changes between two sets of timings, e.g. the stored timings of a machine and a new benchmark run.
+use crate::template::{Day, stats::format_nanos, timings::Timings};
+
+/// Tolerance of `cargo time --check` in percent, unless `--tolerance` is passed.
+pub const DEFAULT_TOLERANCE: f64 = 10.0;
+
+/// The timing of a part before and after a change.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    /// Nanoseconds of the reference, `None` if the part was not benched there.
+    /// Timings include the setup of the part, so that they compare to timings of parts that parsed their own input.
+    pub before: Option<f64>,
+    /// Nanoseconds of the new run, `None` if the part has no timing, e.g. because it failed.
+    pub after: Option<f64>,
+}
+
+/// How a part changed, relative to a tolerance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Improved,
+    Unchanged,
+    Regressed,
+    /// The part has no reference timing.
+    New,
+    /// The part had a reference timing, but no longer has one.
+    Missing,
+}
+
+impl PartDelta {
+    /// Relative change in percent, positive if the part got slower.
+    pub fn change(&self) -> Option<f64> {
+        match (self.before, self.after) {
+            (Some(before), Some(after)) if before > 0.0 => Some((after - before) / before * 100.0),
+            _ => None,
+        }
+    }
+
+    /// Classify the change, `tolerance` is in percent.
+    pub fn verdict(&self, tolerance: f64) -> Verdict {
+        match (self.before, self.after, self.change()) {
+            (_, Some(_), Some(change)) if change > tolerance => Verdict::Regressed,
+            (_, Some(_), Some(change)) if change < -tolerance => Verdict::Improved,
+            (Some(_), Some(_), _) => Verdict::Unchanged,
+            (None, _, _) => Verdict::New,
+            (Some(_), None, _) => Verdict::Missing,
+        }
+    }
+
+    /// Whether the change should fail a check, missing timings fail as well.
+    pub fn is_failure(&self, tolerance: f64) -> bool {
+        matches!(
+            self.verdict(tolerance),
+            Verdict::Regressed | Verdict::Missing
+        )
+    }
+}
+
+/// Compare the parts of all days in `before` and `after`.
+/// A day that is missing from `after`, e.g. because its input is missing or it failed before any part ran, has all
+/// of its stored parts reported as missing.
+pub fn compare(before: &Timings, after: &Timings) -> Vec<PartDelta> {
+    let mut days: Vec<Day> = after
+        .data
+        .iter()
+        .chain(&before.data)
+        .map(|t| t.day)
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let mut deltas = vec![];
+
+    for day in days {
+        let reference = before.data.iter().find(|t| t.day == day);
+        let timing = after.data.iter().find(|t| t.day == day);
+
+        let mut parts: Vec<u8> = timing
+            .iter()
+            .chain(reference.iter())
+            .flat_map(|t| &t.parts)
+            .map(|t| t.part)
+            .collect();
+        parts.sort_unstable();
+        parts.dedup();
+
+        for part in parts {
+            let delta = PartDelta {
+                day,
+                part,
+                before: reference.and_then(|t| t.part_total_nanos(part)),
+                after: timing.and_then(|t| t.part_total_nanos(part)),
+            };
+
+            if delta.before.is_some() || delta.after.is_some() {
+                deltas.push(delta);
+            }
+        }
+    }
+
+    deltas
+}
+
+/// Parse a tolerance in percent, e.g. `10%` or `2.5`.
+pub fn parse_tolerance(value: &str) -> Result<f64, String> {
+    let tolerance: f64 =
+        value.trim().trim_end_matches('%').parse().map_err(|_| {
+            format!("invalid tolerance `{value}`, expected a percentage like `10%`.")
+        })?;
+
+    if tolerance.is_finite() && tolerance >= 0.0 {
+        Ok(tolerance)
+    } else {
+        Err(format!(
+            "invalid tolerance `{value}`, expected a positive percentage."
+        ))
+    }
+}
+
+/// Format the deltas as a table, the columns of the timings are headed by `before` and `after`.
+pub fn format_table(deltas: &[PartDelta], tolerance: f64, before: &str, after: &str) -> String {
+    let nanos = |nanos: Option<f64>| nanos.map_or_else(|| "-".into(), format_nanos);
+
+    let mut lines = vec![format!(
+        "Day  Part  {before:>10}  {after:>10}  {:>8}",
+        "Change"
+    )];
+
+    for delta in deltas {
+        let change = delta
+            .change()
+            .map_or_else(String::new, |change| format!("{change:+.1}%"));
+
+        let verdict = match delta.verdict(tolerance) {
+            Verdict::Improved => "  ✔ improved",
+            Verdict::Unchanged => "",
+            Verdict::Regressed => "  ✖ regressed",
+            Verdict::New => "  new",
+            Verdict::Missing => "  ✖ missing",
+        };
+
+        lines.push(format!(
+            "{:<3}  {:>4}  {:>10}  {:>10}  {change:>8}{verdict}",
+            delta.day.to_string(),
+            delta.part,
+            nanos(delta.before),
+            nanos(delta.after),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Format the deltas as a markdown table for a comparison report.
+pub fn format_markdown(deltas: &[PartDelta], tolerance: f64, before: &str, after: &str) -> String {
+    let nanos = |nanos: Option<f64>| {
+        nanos.map_or_else(|| "`-`".into(), |n| format!("`{}`", format_nanos(n)))
+    };
+
+    let mut lines = vec![
+        format!("| Day | Part | {before} | {after} | Change |"),
+        "| :---: | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for delta in deltas {
+        let change = delta
+            .change()
+            .map_or_else(|| "-".into(), |change| format!("{change:+.1}%"));
+
+        let verdict = match delta.verdict(tolerance) {
+            Verdict::Improved => " ✔",
+            Verdict::Regressed | Verdict::Missing => " ✖",
+            Verdict::Unchanged | Verdict::New => "",
+        };
+
+        lines.push(format!(
+            "| {} | {} | {} | {} | {change}{verdict} |",
+            delta.day.into_inner(),
+            delta.part,
+            nanos(delta.before),
+            nanos(delta.after),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartDelta, Verdict, compare, format_markdown, format_table, parse_tolerance};
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    fn get_mock_timings(part_1: Option<f64>, part_2: Option<f64>) -> Timings {
+        let part = |part, nanos| PartTiming {
+            part,
+            name: None,
+            nanos,
+            stats: None,
+            setup_nanos: None,
+            error: None,
+        };
+
+        Timings {
+            data: vec![Timing {
+                parts: vec![part(1, part_1), part(2, part_2)],
+                total_nanos: part_1.unwrap_or_default() + part_2.unwrap_or_default(),
+                ..Timing::new(day!(8))
+            }],
+        }
+    }
+
+    fn delta(before: Option<f64>, after: Option<f64>) -> PartDelta {
+        PartDelta {
+            day: day!(8),
+            part: 1,
+            before,
+            after,
+        }
+    }
+
+    #[test]
+    fn classifies_changes() {
+        assert_eq!(
+            delta(Some(100.0), Some(111.0)).verdict(10.0),
+            Verdict::Regressed
+        );
+        assert_eq!(
+            delta(Some(100.0), Some(109.0)).verdict(10.0),
+            Verdict::Unchanged
+        );
+        assert_eq!(
+            delta(Some(100.0), Some(80.0)).verdict(10.0),
+            Verdict::Improved
+        );
+        assert_eq!(delta(None, Some(80.0)).verdict(10.0), Verdict::New);
+        assert_eq!(delta(Some(100.0), None).verdict(10.0), Verdict::Missing);
+
+        assert!(delta(Some(100.0), None).is_failure(10.0));
+        assert!(!delta(Some(100.0), Some(80.0)).is_failure(10.0));
+    }
+
+    #[test]
+    fn compares_parts() {
+        let before = get_mock_timings(Some(5_300_000.0), None);
+        let after = get_mock_timings(Some(5_000_000.0), Some(46_700_000.0));
+
+        assert_eq!(
+            compare(&before, &after),
+            vec![
+                PartDelta {
+                    day: day!(8),
+                    part: 1,
+                    before: Some(5_300_000.0),
+                    after: Some(5_000_000.0),
+                },
+                PartDelta {
+                    day: day!(8),
+                    part: 2,
+                    before: None,
+                    after: Some(46_700_000.0),
+                },
+            ]
+        );
+
+        let missing = compare(&before, &Timings::default());
+        assert_eq!(
+            missing,
+            vec![PartDelta {
+                day: day!(8),
+                part: 1,
+                before: Some(5_300_000.0),
+                after: None,
+            }]
+        );
+        assert_eq!(missing[0].verdict(10.0), Verdict::Missing);
+    }
+
+    #[test]
+    fn compares_parts_including_setup() {
+        let before = get_mock_timings(Some(5_000_000.0), None);
+        let mut after = get_mock_timings(Some(1_000_000.0), None);
+        after.data[0].parts[0].setup_nanos = Some(4_000_000.0);
+
+        let deltas = compare(&before, &after);
+        assert_eq!(deltas[0].after, Some(5_000_000.0));
+        assert_eq!(deltas[0].verdict(10.0), Verdict::Unchanged);
+    }
+
+    #[test]
+    fn parses_tolerances() {
+        assert_eq!(parse_tolerance("10%"), Ok(10.0));
+        assert_eq!(parse_tolerance("2.5"), Ok(2.5));
+        assert!(parse_tolerance("-5%").is_err());
+        assert!(parse_tolerance("ten").is_err());
+    }
+
+    #[test]
+    fn formats_tables() {
+        let deltas = [
+            delta(Some(5_300_000.0), Some(5_830_000.0)),
+            delta(None, Some(46_700_000.0)),
+        ];
+
+        assert_eq!(
+            format_table(&deltas, 5.0, "Stored", "Current"),
+            [
+                "Day  Part      Stored     Current    Change",
+                "08      1       5.3ms       5.8ms    +10.0%  ✖ regressed",
+                "08      1           -      46.7ms            new",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn formats_markdown() {
+        let deltas = [
+            delta(Some(5_300_000.0), Some(4_770_000.0)),
+            delta(Some(46_700_000.0), None),
+        ];
+
+        assert_eq!(
+            format_markdown(&deltas, 5.0, "`a1b2c3d`", "Working tree"),
+            [
+                "| Day | Part | `a1b2c3d` | Working tree | Change |",
+                "| :---: | :---: | :---: | :---: | :---: |",
+                "| 8 | 1 | `5.3ms` | `4.8ms` | -10.0% ✔ |",
+                "| 8 | 1 | `46.7ms` | `-` | - ✖ |",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,413 @@
+/// Example cases of a day and their expected answers, see `data/<year>/examples/<day>.json`.
+/// Also extracts example inputs and their expected answers from puzzle descriptions, see `cargo examples`.
+use regex::Regex;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{self, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Context, PuzzleId, data_dir};
+
+/// A named example of a day, e.g. a smaller input that only applies to part two.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExampleCase {
+    pub name: String,
+    /// File name of the input in `data/<year>/examples`, e.g. `11-2.txt`.
+    pub file: String,
+    /// Expected answers, keyed by part.
+    pub answers: BTreeMap<u8, String>,
+    /// Parameters of the case, applied on top of the parameters of the real input.
+    pub params: Context,
+}
+
+impl ExampleCase {
+    /// Read the input of the case.
+    pub fn read_input(&self, puzzle: PuzzleId) -> io::Result<String> {
+        fs::read_to_string(data_dir(puzzle.year).join("examples").join(&self.file))
+    }
+}
+
+/// The example cases of a day, ordered by name.
+/// Can be serialized from JSON, e.g. `{ "default": { "file": "08.txt", "part_1": "40", "params": { "edges": 10 } } }`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExampleManifest {
+    pub cases: Vec<ExampleCase>,
+}
+
+impl ExampleManifest {
+    /// Read the example manifest of a puzzle. If not present, returns a manifest without cases.
+    pub fn read(puzzle: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(puzzle.data_path("examples", "json")) {
+            Ok(contents) => ExampleManifest::try_from(contents),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(ExampleManifest::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ExampleCase> {
+        self.cases.iter().find(|case| case.name == name)
+    }
+}
+
+impl TryFrom<String> for ExampleManifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let cases = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut cases = cases
+            .iter()
+            .map(|(name, case)| {
+                let case = case
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or(format!("expected example `{name}` to be an object."))?;
+
+                let file = case
+                    .get("file")
+                    .and_then(|file| file.get::<String>())
+                    .ok_or(format!("expected `{name}.file` to be a string."))?;
+
+                let params = match case.get("params") {
+                    Some(params) => params
+                        .get::<HashMap<String, JsonValue>>()
+                        .ok_or(format!("expected `{name}.params` to be an object."))?
+                        .iter()
+                        .try_fold(Context::default(), |params, (param, value)| match value {
+                            JsonValue::String(value) => Ok(params.with(param, value)),
+                            JsonValue::Number(value) => Ok(params.with(param, value)),
+                            _ => Err(format!(
+                                "expected `{name}.params.{param}` to be a string or a number."
+                            )),
+                        })?,
+                    None => Context::default(),
+                };
+
+                let answers = case
+                    .iter()
+                    .filter(|(key, _)| *key != "file" && *key != "params")
+                    .map(|(key, answer)| {
+                        let part = key
+                            .strip_prefix("part_")
+                            .and_then(|part| part.parse::<u8>().ok())
+                            .ok_or(format!(
+                                "expected `{name}.{key}` to be a part, e.g. `part_1`."
+                            ))?;
+                        let answer = answer
+                            .get::<String>()
+                            .ok_or(format!("expected `{name}.{key}` to be a string."))?;
+                        Ok((part, answer.clone()))
+                    })
+                    .collect::<Result<_, String>>()?;
+
+                Ok(ExampleCase {
+                    name: name.clone(),
+                    file: file.clone(),
+                    answers,
+                    params,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        cases.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        Ok(ExampleManifest { cases })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The example of a part as found in the puzzle description.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartExample {
+    /// The example input, if the part introduces one.
+    pub input: Option<String>,
+    /// The expected answer of the example, i.e. the last emphasized code of the part.
+    pub answer: Option<String>,
+}
+
+/// Extract the examples of all parts from a puzzle description in markdown, in order of the parts.
+/// Part two only has an input if it introduces a new example.
+pub fn extract(markdown: &str) -> Vec<PartExample> {
+    let mut parts: Vec<PartExample> = split_parts(markdown)
+        .into_iter()
+        .enumerate()
+        .map(|(i, section)| PartExample {
+            input: example_block(section, i == 0),
+            answer: last_emphasized_code(section),
+        })
+        .collect();
+
+    if parts.len() > 1 && parts[1].input == parts[0].input {
+        parts[1].input = None;
+    }
+
+    parts
+}
+
+fn split_parts(markdown: &str) -> Vec<&str> {
+    let part_two = markdown.match_indices("## ").find(|(i, _)| {
+        markdown[*i..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .contains("Part Two")
+    });
+
+    match part_two {
+        Some((i, _)) => vec![&markdown[..i], &markdown[i..]],
+        None => vec![markdown],
+    }
+}
+
+/// The first code block that follows a paragraph mentioning an example.
+/// With `fallback`, the first code block is used if no block is introduced like that.
+fn example_block(section: &str, fallback: bool) -> Option<String> {
+    let mut blocks: Vec<(bool, String)> = vec![];
+    let mut previous_line = "";
+    let mut current: Option<(bool, Vec<&str>)> = None;
+
+    for line in section.lines() {
+        if line.starts_with("```") {
+            match current.take() {
+                Some((introduced, lines)) => {
+                    blocks.push((introduced, lines.join("\n") + "\n"));
+                }
+                None => {
+                    current = Some((previous_line.to_lowercase().contains("example"), vec![]));
+                }
+            }
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line);
+        } else if !line.trim().is_empty() {
+            previous_line = line;
+        }
+    }
+
+    let introduced = blocks.iter().find(|(introduced, _)| *introduced);
+    let block = if fallback {
+        introduced.or(blocks.first())
+    } else {
+        introduced
+    };
+
+    block.map(|(_, block)| block.clone())
+}
+
+fn last_emphasized_code(section: &str) -> Option<String> {
+    let re = Regex::new(r"`\*([^`*]+)\*`|\*`([^`*]+)`\*").unwrap();
+    re.captures_iter(section)
+        .last()
+        .and_then(|captures| captures.get(1).or(captures.get(2)))
+        .map(|answer| answer.as_str().to_string())
+}
+
+/// Fill the expected answer into the scaffolded test of a part, returning the updated module.
+/// With `example_part`, the test reads the example file of that part, e.g. `01-2.txt`.
+/// Returns `None` if the test is missing or already has an expected answer.
+pub fn fill_test(module: &str, part: u8, answer: &str, example_part: Option<u8>) -> Option<String> {
+    let name = match part {
+        1 => "test_part_one",
+        2 => "test_part_two",
+        _ => return None,
+    };
+
+    let start = module.find(&format!("fn {name}()"))?;
+    let end = module[start + 3..]
+        .find("fn ")
+        .map_or(module.len(), |i| start + 3 + i);
+
+    // NOTE: tests of parts that return a `Result` compare its `ok()` value.
+    let body = &module[start..end];
+    let unfilled = [
+        "assert_eq!(result, None);",
+        "assert_eq!(result.ok(), None);",
+    ]
+    .into_iter()
+    .find(|assertion| body.contains(assertion))?;
+
+    let mut body = body.replace(
+        unfilled,
+        &unfilled.replace("None", &format!("Some({answer})")),
+    );
+
+    if let Some(example_part) = example_part {
+        body = body.replace(
+            "read_file(\"examples\", PUZZLE)",
+            &format!("read_file_part(\"examples\", PUZZLE, {example_part})"),
+        );
+    }
+
+    Some(format!("{}{body}{}", &module[..start], &module[end..]))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ExampleManifest, PartExample, extract, fill_test};
+    use crate::template::Context;
+
+    const MODULE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+    #[test]
+    fn extracts_example_and_answers() {
+        let markdown = [
+            "## --- Day 1: Test ---",
+            "",
+            "The dial is shown like this:",
+            "",
+            "```",
+            "0 1 2",
+            "```",
+            "",
+            "For example:",
+            "",
+            "```",
+            "L68",
+            "R48",
+            "```",
+            "",
+            "After `*1*` rotation the dial points at `*50*`. The password is `*3*`.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Count every click. In the example above, the password is *`6`*.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            extract(&markdown),
+            vec![
+                PartExample {
+                    input: Some("L68\nR48\n".into()),
+                    answer: Some("3".into()),
+                },
+                PartExample {
+                    input: None,
+                    answer: Some("6".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_second_example() {
+        let markdown = [
+            "```",
+            "a",
+            "```",
+            "",
+            "The answer is `*1*`.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Here's a new example:",
+            "",
+            "```",
+            "b",
+            "```",
+            "",
+            "The answer is `*2*`.",
+        ]
+        .join("\n");
+
+        let examples = extract(&markdown);
+        assert_eq!(examples[0].input, Some("a\n".into()));
+        assert_eq!(examples[1].input, Some("b\n".into()));
+        assert_eq!(examples[1].answer, Some("2".into()));
+    }
+
+    #[test]
+    fn extracts_first_part_only() {
+        let examples = extract("For example:\n\n```\nx\n```\n\nThe answer is `*42*`.\n");
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].answer, Some("42".into()));
+    }
+
+    #[test]
+    fn fills_tests() {
+        let module = fill_test(MODULE, 1, "42", None).unwrap();
+        let module = fill_test(&module, 2, "1337", Some(2)).unwrap();
+
+        assert!(module.contains(
+            "part_one(&advent_of_code::template::read_file(\"examples\", PUZZLE));\n        assert_eq!(result, Some(42));"
+        ));
+        assert!(module.contains(
+            "part_two(&advent_of_code::template::read_file_part(\"examples\", PUZZLE, 2));\n        assert_eq!(result, Some(1337));"
+        ));
+    }
+
+    #[test]
+    fn fills_tests_of_result_parts() {
+        let module = "fn test_part_one() {\n    let result = part_one(\"\");\n    assert_eq!(result.ok(), None);\n}\n";
+        assert_eq!(
+            fill_test(module, 1, "42", None).unwrap(),
+            "fn test_part_one() {\n    let result = part_one(\"\");\n    assert_eq!(result.ok(), Some(42));\n}\n"
+        );
+    }
+
+    #[test]
+    fn keeps_filled_tests() {
+        let module = fill_test(MODULE, 1, "42", None).unwrap();
+        assert_eq!(fill_test(&module, 1, "43", None), None);
+        assert_eq!(fill_test(&module, 3, "43", None), None);
+    }
+
+    #[test]
+    fn reads_manifest() {
+        let manifest = ExampleManifest::try_from(
+            r#"{ "servers": { "file": "11-2.txt", "part_2": "2" }, "default": { "file": "11.txt", "part_1": "5" } }"#
+                .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest
+                .cases
+                .iter()
+                .map(|case| case.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["default", "servers"]
+        );
+
+        let servers = manifest.get("servers").unwrap();
+        assert_eq!(servers.file, "11-2.txt");
+        assert_eq!(servers.answers.get(&2).map(String::as_str), Some("2"));
+        assert_eq!(servers.answers.get(&1), None);
+        assert_eq!(servers.params, Context::default());
+    }
+
+    #[test]
+    fn reads_params() {
+        let manifest = ExampleManifest::try_from(
+            r#"{ "default": { "file": "08.txt", "part_1": "40", "params": { "edges": 10, "mode": "fast" } } }"#
+                .to_string(),
+        )
+        .unwrap();
+
+        let params = &manifest.get("default").unwrap().params;
+        assert_eq!(params.param::<usize>("edges"), Some(10));
+        assert_eq!(params.param::<String>("mode"), Some("fast".into()));
+        assert_eq!(manifest.get("default").unwrap().answers.len(), 1);
+    }
+
+    #[test]
+    fn rejects_malformed_manifest() {
+        assert!(ExampleManifest::try_from(r#"{ "a": { "part_1": "5" } }"#.to_string()).is_err());
+        assert!(
+            ExampleManifest::try_from(r#"{ "a": { "file": "a.txt", "part_1": 5 } }"#.to_string())
+                .is_err()
+        );
+        assert!(
+            ExampleManifest::try_from(r#"{ "a": { "file": "a.txt", "one": "5" } }"#.to_string())
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,292 @@
+/// Heap profiles of solutions, recorded from the DHAT output of `cargo solve <day> --dhat`.
+use std::{collections::HashMap, fs, io::ErrorKind, str::FromStr};
+use tinyjson::JsonValue;
+
+/// Allocations of a day, summed over its parts.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HeapProfile {
+    /// Bytes allocated over the whole run.
+    pub total_bytes: u64,
+    /// Number of allocations over the whole run.
+    pub total_blocks: u64,
+    /// Bytes live at the peak of the heap.
+    pub peak_bytes: u64,
+}
+
+impl HeapProfile {
+    /// Parse the profile from a `dhat-heap.json` file, summing the program points of the heap.
+    pub fn from_dhat_json(value: &str) -> Result<Self, String> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected DHAT document to be an object.")?;
+
+        if json
+            .get("mode")
+            .and_then(|mode| mode.get::<String>())
+            .map(String::as_str)
+            != Some("rust-heap")
+        {
+            return Err("expected a DHAT heap profile.".into());
+        }
+
+        let points = json
+            .get("pps")
+            .and_then(|pps| pps.get::<Vec<JsonValue>>())
+            .ok_or("expected DHAT document to have an array `pps`.")?;
+
+        let mut profile = HeapProfile::default();
+
+        for point in points {
+            let point = point
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected program point to be an object.")?;
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let number = |key: &str| {
+                point
+                    .get(key)
+                    .and_then(|v| v.get::<f64>())
+                    .map_or(0, |v| *v as u64)
+            };
+
+            profile.total_bytes += number("tb");
+            profile.total_blocks += number("tbk");
+            profile.peak_bytes += number("gb");
+        }
+
+        Ok(profile)
+    }
+
+    /// Combine the profiles of two parts. Parts run one after the other, so the peak is the larger of both.
+    #[must_use]
+    pub fn combine(&self, other: &Self) -> Self {
+        HeapProfile {
+            total_bytes: self.total_bytes + other.total_bytes,
+            total_blocks: self.total_blocks + other.total_blocks,
+            peak_bytes: self.peak_bytes.max(other.peak_bytes),
+        }
+    }
+}
+
+/// The file DHAT writes the profile of a part to, relative to the repository root.
+pub fn dhat_file_name(part: u8) -> String {
+    format!("dhat-heap-{part}.json")
+}
+
+/// The part a DHAT profile was written for, if the file name is one of [`dhat_file_name`].
+fn dhat_file_part(file_name: &str) -> Option<u8> {
+    file_name
+        .strip_prefix("dhat-heap-")?
+        .strip_suffix(".json")?
+        .parse()
+        .ok()
+}
+
+/// The parts with a DHAT profile in the repository root, including extra parts registered with `solution!`.
+pub fn dhat_parts() -> Vec<u8> {
+    let Ok(entries) = fs::read_dir(".") else {
+        return Vec::new();
+    };
+
+    let mut parts: Vec<u8> = entries
+        .filter_map(|entry| dhat_file_part(entry.ok()?.file_name().to_str()?))
+        .collect();
+
+    parts.sort_unstable();
+    parts
+}
+
+/// Read the DHAT profiles of the given parts, combined. `None` if no part was profiled.
+pub fn read_dhat_profiles(parts: &[u8]) -> Result<Option<HeapProfile>, String> {
+    let mut combined: Option<HeapProfile> = None;
+
+    for &part in parts {
+        let file_name = dhat_file_name(part);
+
+        let contents = match fs::read_to_string(&file_name) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("{file_name}: {e}")),
+        };
+
+        let profile =
+            HeapProfile::from_dhat_json(&contents).map_err(|e| format!("{file_name}: {e}"))?;
+
+        combined = Some(combined.map_or(profile, |combined| combined.combine(&profile)));
+    }
+
+    Ok(combined)
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = "B";
+
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&HeapProfile> for JsonValue {
+    fn from(value: &HeapProfile) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "total_blocks".into(),
+            JsonValue::Number(value.total_blocks as f64),
+        );
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HeapProfile {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap profile to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("Expected heap.{key} to be a number."))
+        };
+
+        Ok(HeapProfile {
+            total_bytes: number("total_bytes")?,
+            total_blocks: number("total_blocks")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
+/// Serialize an optional heap profile, using `null` if absent.
+pub fn heap_to_json(heap: Option<&HeapProfile>) -> JsonValue {
+    heap.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/// Deserialize an optional heap profile. A missing key or `null` are treated as absent.
+pub fn heap_from_json(value: Option<&JsonValue>) -> Result<Option<HeapProfile>, String> {
+    match value {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(v) => HeapProfile::try_from(v).map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{HeapProfile, dhat_file_part, format_bytes, heap_from_json, heap_to_json};
+
+    #[test]
+    fn parses_parts_of_dhat_files() {
+        assert_eq!(dhat_file_part("dhat-heap-1.json"), Some(1));
+        assert_eq!(dhat_file_part("dhat-heap-3.json"), Some(3));
+        assert_eq!(dhat_file_part("dhat-heap.json"), None);
+        assert_eq!(dhat_file_part("dhat-heap-x.json"), None);
+        assert_eq!(dhat_file_part("dhat-heap-1.json.bak"), None);
+    }
+
+    #[test]
+    fn parses_dhat_profiles() {
+        let json = r#"{
+            "dhatFileVersion": 2, "mode": "rust-heap", "verb": "Allocated", "bklt": true, "bkacc": false,
+            "tu": "µs", "Mtu": "s", "tuth": 10, "cmd": "target/dhat/2025_01", "pid": 1, "tg": 12, "te": 20,
+            "pps": [
+                { "tb": 1024, "tbk": 2, "tl": 10, "mb": 1024, "mbk": 1, "gb": 512, "gbk": 1, "eb": 0, "ebk": 0, "fs": [1] },
+                { "tb": 100, "tbk": 1, "tl": 3, "mb": 100, "mbk": 1, "gb": 100, "gbk": 1, "eb": 0, "ebk": 0, "fs": [2] }
+            ],
+            "ftbl": ["[root]", "main", "part_one"]
+        }"#;
+
+        assert_eq!(
+            HeapProfile::from_dhat_json(json),
+            Ok(HeapProfile {
+                total_bytes: 1124,
+                total_blocks: 3,
+                peak_bytes: 612,
+            })
+        );
+
+        assert!(HeapProfile::from_dhat_json(r#"{ "mode": "ad-hoc", "pps": [] }"#).is_err());
+        assert!(HeapProfile::from_dhat_json("not json").is_err());
+    }
+
+    #[test]
+    fn combines_parts() {
+        let part_1 = HeapProfile {
+            total_bytes: 100,
+            total_blocks: 2,
+            peak_bytes: 80,
+        };
+        let part_2 = HeapProfile {
+            total_bytes: 50,
+            total_blocks: 5,
+            peak_bytes: 20,
+        };
+
+        assert_eq!(
+            part_1.combine(&part_2),
+            HeapProfile {
+                total_bytes: 150,
+                total_blocks: 7,
+                peak_bytes: 80,
+            }
+        );
+    }
+
+    #[test]
+    fn roundtrips_profiles() {
+        let heap = HeapProfile {
+            total_bytes: 4096,
+            total_blocks: 12,
+            peak_bytes: 2048,
+        };
+
+        assert_eq!(
+            heap_from_json(Some(&heap_to_json(Some(&heap)))),
+            Ok(Some(heap))
+        );
+        assert_eq!(heap_from_json(None), Ok(None));
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
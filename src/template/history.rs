@@ -0,0 +1,427 @@
+/// Append-only history of benchmark results.
+/// Unlike the stored timings of a machine, which only holds the latest numbers, every benched part is recorded
+/// together with the state of the repository it was benched at.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day, Year, data_dir, machine::MachineProfile, part_label, protocol::PartReport,
+};
+
+static HISTORY_FILE_NAME: &str = "timings-history.jsonl";
+
+/// A single benchmark result of a part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub day: Day,
+    pub part: u8,
+    /// The name of the function implementing the part, `None` in entries recorded before parts were named.
+    pub name: Option<String>,
+    pub nanos: u128,
+    pub samples: u128,
+    /// The execution time of the setup of the part, excluded from `nanos`. Only present for days with a `setup`.
+    pub setup_nanos: Option<u64>,
+    /// The commit hash of `HEAD`, `None` if it could not be determined.
+    pub commit: Option<String>,
+    /// Whether the sources had uncommitted changes.
+    pub dirty: bool,
+    pub rustc: String,
+    /// The id of the machine the part was benched on, `None` for entries recorded before machines were tracked.
+    pub machine: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// The state of the repository and toolchain a benchmark was run with.
+#[derive(Clone, Debug)]
+pub struct BuildInfo {
+    pub commit: Option<String>,
+    pub dirty: bool,
+    pub rustc: String,
+    pub machine: String,
+    pub timestamp: u64,
+}
+
+impl BuildInfo {
+    pub fn detect() -> Self {
+        let commit = git(&["rev-parse", "HEAD"]);
+
+        // NOTE: only changes to the sources count, files written by `time --store` would always mark the tree dirty otherwise.
+        let dirty = git(&[
+            "status",
+            "--porcelain",
+            "--",
+            "src",
+            "build.rs",
+            "Cargo.toml",
+            "Cargo.lock",
+            ".cargo",
+        ])
+        .is_some_and(|status| !status.is_empty());
+
+        Self {
+            commit,
+            dirty,
+            rustc: env!("AOC_RUSTC_VERSION").into(),
+            machine: MachineProfile::detect().id(),
+            timestamp: unix_timestamp(),
+        }
+    }
+}
+
+/// Seconds since the unix epoch.
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Run git with `args`, returns its trimmed output or `None` if it failed.
+pub fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+impl HistoryEntry {
+    /// Create history entries for all benched parts with an answer.
+    pub fn from_reports(reports: &[PartReport], info: &BuildInfo) -> Vec<Self> {
+        reports
+            .iter()
+            .filter(|report| report.answer.is_some() && report.stats.is_some())
+            .filter_map(|report| {
+                Some(Self {
+                    day: report.puzzle.day,
+                    part: report.part?,
+                    name: report.name.clone(),
+                    nanos: report.nanos,
+                    samples: report.samples,
+                    setup_nanos: report.setup_nanos,
+                    commit: info.commit.clone(),
+                    dirty: info.dirty,
+                    rustc: info.rustc.clone(),
+                    machine: Some(info.machine.clone()),
+                    timestamp: info.timestamp,
+                })
+            })
+            .collect()
+    }
+
+    /// How the part is shown, e.g. `Part 1` or the name of an extra part.
+    pub fn label(&self) -> String {
+        part_label(self.part, self.name.as_deref())
+    }
+
+    /// The execution time including the setup, comparable to entries recorded before days could have a setup.
+    pub fn total_nanos(&self) -> u128 {
+        self.nanos + u128::from(self.setup_nanos.unwrap_or_default())
+    }
+
+    /// A short label for the state of the sources, e.g. `a1b2c3d*` for uncommitted changes on top of `a1b2c3d`.
+    pub fn revision(&self) -> String {
+        let commit = self
+            .commit
+            .as_deref()
+            .map_or("unknown", |c| &c[..c.len().min(7)]);
+        format!("{commit}{}", if self.dirty { "*" } else { "" })
+    }
+}
+
+/// Append entries to the history file of a year.
+pub fn append(year: Year, entries: &[HistoryEntry]) -> Result<(), io::Error> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(data_dir(year))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(data_dir(year).join(HISTORY_FILE_NAME))?;
+
+    let mut lines = String::new();
+
+    for entry in entries {
+        let line = JsonValue::from(entry)
+            .stringify()
+            .map_err(io::Error::other)?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+
+    file.write_all(lines.as_bytes())
+}
+
+/// Read all entries of the history file of a year. If not present, returns an empty history.
+pub fn read_from_file(year: Year) -> Vec<HistoryEntry> {
+    fs::read_to_string(data_dir(year).join(HISTORY_FILE_NAME))
+        .map(|contents| parse(&contents))
+        .unwrap_or_default()
+}
+
+fn parse(contents: &str) -> Vec<HistoryEntry> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(i, line)| {
+            let entry = JsonValue::from_str(line)
+                .map_err(|e| e.to_string())
+                .and_then(|json| HistoryEntry::try_from(&json));
+
+            match entry {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    eprintln!("Skipping line {} of {HISTORY_FILE_NAME}: {e}", i + 1);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Format seconds since the unix epoch as a UTC date, e.g. `2025-12-08 10:31`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "name".into(),
+            value
+                .name
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "setup_nanos".into(),
+            value
+                .setup_nanos
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("dirty".into(), JsonValue::Boolean(value.dirty));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert(
+            "machine".into(),
+            value
+                .machine
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected entry.{key} to be a number."))
+        };
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| v.get::<String>().cloned())
+            .ok_or("Expected entry.commit to be null or string.")?;
+
+        let dirty = json
+            .get("dirty")
+            .and_then(|v| v.get::<bool>().copied())
+            .ok_or("Expected entry.dirty to be a boolean.")?;
+
+        let rustc = json
+            .get("rustc")
+            .and_then(|v| v.get::<String>().cloned())
+            .ok_or("Expected entry.rustc to be a string.")?;
+
+        // NOTE: the machine is optional to support entries recorded before it was introduced.
+        let machine = json.get("machine").and_then(|v| v.get::<String>().cloned());
+
+        // NOTE: the name is optional to support entries recorded before parts were named.
+        let name = json.get("name").and_then(|v| v.get::<String>().cloned());
+
+        // NOTE: the setup is optional to support entries recorded before days could have a setup.
+        let setup_nanos = json
+            .get("setup_nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64);
+
+        Ok(HistoryEntry {
+            day,
+            part: number("part")? as u8,
+            name,
+            nanos: number("nanos")? as u128,
+            samples: number("samples")? as u128,
+            setup_nanos,
+            commit,
+            dirty,
+            rustc,
+            machine,
+            timestamp: number("timestamp")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{HistoryEntry, format_timestamp, parse};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    fn get_mock_entry(commit: Option<&str>, dirty: bool) -> HistoryEntry {
+        HistoryEntry {
+            day: day!(8),
+            part: 1,
+            name: Some("part_one".into()),
+            nanos: 5_300_000,
+            samples: 188,
+            setup_nanos: None,
+            commit: commit.map(Into::into),
+            dirty,
+            rustc: "rustc 1.91.0-nightly (6c699a372 2025-09-05)".into(),
+            machine: Some("apple-m1-pro-10c-macos-aarch64".into()),
+            timestamp: 1_765_189_860,
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = get_mock_entry(Some("a1b2c3d4e5f6"), true);
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        assert_eq!(
+            parse(&format!("{line}\n\n{line}\n")),
+            vec![entry.clone(), entry]
+        );
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let entry = get_mock_entry(None, false);
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        assert_eq!(
+            parse(&format!("{{ \"day\": \"08\" }}\n{line}")),
+            vec![entry]
+        );
+    }
+
+    #[test]
+    fn reads_entries_without_machine() {
+        let line = r#"{ "day": "08", "part": 2, "nanos": 41000, "samples": 10000, "commit": null, "dirty": false, "rustc": "rustc 1.91.0", "timestamp": 0 }"#;
+        let entries = parse(line);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].machine, None);
+        assert_eq!(entries[0].setup_nanos, None);
+        assert_eq!(entries[0].name, None);
+    }
+
+    #[test]
+    fn labels_extra_parts() {
+        let mut entry = get_mock_entry(None, false);
+        assert_eq!(entry.label(), "Part 1");
+
+        entry.part = 3;
+        entry.name = Some("part_two_brute_force".into());
+        assert_eq!(entry.label(), "part_two_brute_force");
+
+        entry.name = None;
+        assert_eq!(entry.label(), "Part 3");
+    }
+
+    #[test]
+    fn roundtrips_setup_times() {
+        let mut entry = get_mock_entry(None, false);
+        entry.setup_nanos = Some(1_200_000);
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        assert_eq!(parse(&line), vec![entry.clone()]);
+        assert_eq!(entry.total_nanos(), 6_500_000);
+    }
+
+    #[test]
+    fn formats_revisions() {
+        assert_eq!(
+            get_mock_entry(Some("a1b2c3d4e5f6"), false).revision(),
+            "a1b2c3d"
+        );
+        assert_eq!(
+            get_mock_entry(Some("a1b2c3d4e5f6"), true).revision(),
+            "a1b2c3d*"
+        );
+        assert_eq!(get_mock_entry(None, false).revision(), "unknown");
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_765_189_860), "2025-12-08 10:31");
+        assert_eq!(format_timestamp(951_825_600), "2000-02-29 12:00");
+    }
+}
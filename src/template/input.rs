@@ -0,0 +1,235 @@
+/// Sources the input of a run can be read from.
+use std::{
+    env, fmt, fs,
+    io::{self, ErrorKind, Read},
+    path::PathBuf,
+};
+
+use crate::template::examples::ExampleManifest;
+use crate::template::{Context, PuzzleId};
+
+/// Where the input of a run comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputSource {
+    /// The personal puzzle input, `data/<year>/inputs/<day>.txt`.
+    Puzzle,
+    /// An arbitrary file, e.g. passed with `--input <path>`.
+    Path(PathBuf),
+    /// Standard input, passed with `--input -`.
+    Stdin,
+    /// A named case of the examples manifest, passed with `--example <name>`.
+    Example(String),
+    /// The puzzle input embedded at compile time, declared with `input = include_str!(..)` in `solution!`.
+    Embedded(&'static str),
+}
+
+impl InputSource {
+    /// Parse the source from the `--input <path|->` and `--example <name>` arguments of a run.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let value = |flag: &str| match args.iter().position(|x| x == flag) {
+            None => Ok(None),
+            Some(index) => match args.get(index + 1) {
+                Some(value) => Ok(Some(value.as_str())),
+                None => Err(format!(
+                    "Unexpected command-line input. Format: cargo solve 1 {flag} <value>"
+                )),
+            },
+        };
+
+        match (value("--input")?, value("--example")?) {
+            (Some(_), Some(_)) => Err("`--input` can't be combined with `--example`.".into()),
+            (Some("-"), None) => Ok(InputSource::Stdin),
+            (Some(path), None) => Ok(InputSource::Path(path.into())),
+            (None, Some(name)) => Ok(InputSource::Example(name.to_string())),
+            (None, None) => Ok(InputSource::Puzzle),
+        }
+    }
+
+    /// Read the puzzle input from `embedded` instead of the data folder, if the day embeds it.
+    #[must_use]
+    pub fn with_embedded(self, embedded: Option<&'static str>) -> Self {
+        match (self, embedded) {
+            (InputSource::Puzzle, Some(input)) => InputSource::Embedded(input),
+            (source, _) => source,
+        }
+    }
+
+    /// Whether the source is the personal puzzle input, the only one whose answers can be submitted.
+    pub fn is_puzzle(&self) -> bool {
+        matches!(self, InputSource::Puzzle | InputSource::Embedded(_))
+    }
+
+    /// Load the input of `puzzle` and the parameters that come with it. Only example cases declare parameters.
+    pub fn load(&self, puzzle: PuzzleId) -> Result<(String, Context), InputError> {
+        let read = |path: PathBuf| {
+            fs::read_to_string(&path).map_err(|error| match error.kind() {
+                ErrorKind::NotFound => InputError::Missing {
+                    path,
+                    puzzle: matches!(self, InputSource::Puzzle).then_some(puzzle),
+                },
+                _ => InputError::Io { path, error },
+            })
+        };
+
+        match self {
+            InputSource::Puzzle => {
+                let cwd = env::current_dir().map_err(InputError::Cwd)?;
+                Ok((
+                    read(cwd.join(puzzle.data_path("inputs", "txt")))?,
+                    Context::default(),
+                ))
+            }
+            InputSource::Path(path) => Ok((read(path.clone())?, Context::default())),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok((input, Context::default()))
+            }
+            InputSource::Example(name) => {
+                let manifest = ExampleManifest::read(puzzle).map_err(|e| {
+                    InputError::Example(format!("failed to read the examples of {puzzle}: {e}"))
+                })?;
+
+                let case = manifest.get(name).ok_or_else(|| {
+                    InputError::Example(format!(
+                        "no example named `{name}` in {}.",
+                        puzzle.data_path("examples", "json").display()
+                    ))
+                })?;
+
+                let input = case.read_input(puzzle).map_err(|e| {
+                    InputError::Example(format!(
+                        "failed to read the input of example `{name}`: {e}"
+                    ))
+                })?;
+
+                Ok((input, case.params.clone()))
+            }
+            InputSource::Embedded(input) => Ok(((*input).to_string(), Context::default())),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug)]
+pub enum InputError {
+    /// The file does not exist. Carries the puzzle if it is a puzzle input, which can be downloaded.
+    Missing {
+        path: PathBuf,
+        puzzle: Option<PuzzleId>,
+    },
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Cwd(io::Error),
+    Stdin(io::Error),
+    Example(String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing {
+                path,
+                puzzle: Some(puzzle),
+            } => write!(
+                f,
+                "Input file \"{}\" does not exist. Download it with `cargo download {} --year {}`.",
+                path.display(),
+                puzzle.day,
+                puzzle.year
+            ),
+            InputError::Missing { path, puzzle: None } => {
+                write!(f, "Input file \"{}\" does not exist.", path.display())
+            }
+            InputError::Io { path, error } => {
+                write!(
+                    f,
+                    "Failed to read input file \"{}\": {error}",
+                    path.display()
+                )
+            }
+            InputError::Cwd(e) => write!(f, "Failed to resolve the current directory: {e}"),
+            InputError::Stdin(e) => write!(f, "Failed to read the input from stdin: {e}"),
+            InputError::Example(e) => write!(f, "Failed to load example: {e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputError, InputSource};
+    use crate::template::PuzzleId;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(
+            InputSource::from_args(&args(&["2025_01"])),
+            Ok(InputSource::Puzzle)
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["2025_01", "--input", "-"])),
+            Ok(InputSource::Stdin)
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["2025_01", "--input", "big.txt"])),
+            Ok(InputSource::Path("big.txt".into()))
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["2025_01", "--example", "servers"])),
+            Ok(InputSource::Example("servers".into()))
+        );
+        assert!(InputSource::from_args(&args(&["2025_01", "--input"])).is_err());
+        assert!(
+            InputSource::from_args(&args(&["2025_01", "--input", "-", "--example", "a"])).is_err()
+        );
+    }
+
+    #[test]
+    fn loads_embedded_input() {
+        let puzzle: PuzzleId = "2025/01".parse().unwrap();
+        let source = InputSource::Puzzle.with_embedded(Some("L68\nR48"));
+        assert_eq!(source, InputSource::Embedded("L68\nR48"));
+        assert!(source.is_puzzle());
+
+        let (input, ctx) = source.load(puzzle).unwrap();
+        assert_eq!(input, "L68\nR48");
+        assert_eq!(ctx, Default::default());
+
+        // NOTE: other sources selected on the command line take precedence over the embedded input.
+        assert_eq!(
+            InputSource::Stdin.with_embedded(Some("L68\nR48")),
+            InputSource::Stdin
+        );
+        assert_eq!(InputSource::Puzzle.with_embedded(None), InputSource::Puzzle);
+    }
+
+    #[test]
+    fn names_missing_inputs() {
+        let puzzle: PuzzleId = "2025/01".parse().unwrap();
+
+        let path = std::env::temp_dir().join("aoc-missing-input.txt");
+        let error = InputSource::Path(path.clone()).load(puzzle).unwrap_err();
+        assert!(matches!(error, InputError::Missing { puzzle: None, .. }));
+        assert!(error.to_string().contains(&path.display().to_string()));
+
+        let error = InputError::Missing {
+            path: "data/2025/inputs/01.txt".into(),
+            puzzle: Some(puzzle),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Input file \"data/2025/inputs/01.txt\" does not exist. Download it with `cargo download 01 --year 2025`."
+        );
+    }
+}
@@ -0,0 +1,203 @@
+/// The machine benchmarks are run on. Timings are stored per machine, so that results of different machines
+/// don't overwrite each other.
+use std::{collections::HashMap, env, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+/// Hardware and toolchain a benchmark was run with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MachineProfile {
+    /// The CPU model, e.g. `AMD Ryzen 7 5800X 8-Core Processor`.
+    pub cpu: String,
+    /// The number of logical cores.
+    pub cores: usize,
+    /// The operating system and architecture, e.g. `linux x86_64`.
+    pub os: String,
+    pub rustc: String,
+    /// The `target-cpu` and `target-feature` codegen flags the solutions were built with, `default` if none.
+    pub target_cpu: String,
+}
+
+impl MachineProfile {
+    pub fn detect() -> Self {
+        Self {
+            cpu: detect_cpu().unwrap_or_else(|| "unknown CPU".into()),
+            cores: thread::available_parallelism().map_or(1, usize::from),
+            os: format!("{} {}", env::consts::OS, env::consts::ARCH),
+            rustc: env!("AOC_RUSTC_VERSION").into(),
+            target_cpu: env!("AOC_TARGET_CPU").into(),
+        }
+    }
+
+    /// Identifies the machine, used as file name of its timings, e.g. `amd-ryzen-7-5800x-8-core-processor-16c-linux-x86_64`.
+    /// The compiler version is not part of it, toolchain updates keep their history on the same machine.
+    pub fn id(&self) -> String {
+        let mut source = format!("{} {}c {}", self.cpu, self.cores, self.os);
+        if self.target_cpu != "default" {
+            source.push(' ');
+            source.push_str(&self.target_cpu);
+        }
+
+        let mut id = String::new();
+
+        for c in source.chars() {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                id.push(c.to_ascii_lowercase());
+            } else if !id.is_empty() && !id.ends_with('-') {
+                id.push('-');
+            }
+        }
+
+        id.trim_end_matches('-').to_string()
+    }
+
+    /// A human-readable description, e.g. `AMD Ryzen 7 5800X 8-Core Processor, 16 cores, linux x86_64`.
+    pub fn label(&self) -> String {
+        let cores = if self.cores == 1 { "core" } else { "cores" };
+        format!("{}, {} {cores}, {}", self.cpu, self.cores, self.os)
+    }
+}
+
+fn detect_cpu() -> Option<String> {
+    if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+        return parse_cpuinfo(&cpuinfo);
+    }
+
+    // NOTE: macOS has no `/proc`, ask the kernel instead.
+    let output = Command::new("sysctl")
+        .args(["-n", "machdep.cpu.brand_string"])
+        .output()
+        .ok()?;
+    let cpu = String::from_utf8(output.stdout).ok()?;
+    (!cpu.trim().is_empty()).then(|| cpu.trim().to_string())
+}
+
+/// Read the CPU model from the contents of `/proc/cpuinfo`. ARM machines may only report the hardware.
+fn parse_cpuinfo(cpuinfo: &str) -> Option<String> {
+    ["model name", "Model", "Hardware"].iter().find_map(|key| {
+        cpuinfo.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == *key && !value.trim().is_empty())
+                .then(|| value.split_whitespace().collect::<Vec<_>>().join(" "))
+        })
+    })
+}
+
+/// The machine whose timings are shown in the readme, set with `AOC_README_MACHINE=<id>`. `None` shows all machines.
+pub fn readme_machine() -> Option<String> {
+    env::var("AOC_README_MACHINE")
+        .ok()
+        .filter(|id| !id.trim().is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&MachineProfile> for JsonValue {
+    fn from(value: &MachineProfile) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("os".into(), JsonValue::String(value.os.clone()));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert(
+            "target_cpu".into(),
+            JsonValue::String(value.target_cpu.clone()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MachineProfile {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected machine profile to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>().cloned())
+                .ok_or(format!("Expected profile.{key} to be a string."))
+        };
+
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected profile.cores to be a number.")?;
+
+        Ok(MachineProfile {
+            cpu: string("cpu")?,
+            cores: cores as usize,
+            os: string("os")?,
+            rustc: string("rustc")?,
+            target_cpu: string("target_cpu")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MachineProfile, parse_cpuinfo};
+    use tinyjson::JsonValue;
+
+    fn get_mock_profile() -> MachineProfile {
+        MachineProfile {
+            cpu: "AMD Ryzen 7 5800X 8-Core Processor".into(),
+            cores: 16,
+            os: "linux x86_64".into(),
+            rustc: "rustc 1.91.0-nightly (6c699a372 2025-09-05)".into(),
+            target_cpu: "default".into(),
+        }
+    }
+
+    #[test]
+    fn parses_cpuinfo() {
+        let x86 = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD Ryzen 7 5800X  8-Core Processor\nflags\t\t: fpu vme";
+        assert_eq!(
+            parse_cpuinfo(x86),
+            Some("AMD Ryzen 7 5800X 8-Core Processor".into())
+        );
+
+        let arm = "processor\t: 0\nBogoMIPS\t: 108.00\n\nHardware\t: BCM2835\nModel\t\t: Raspberry Pi 4 Model B Rev 1.4";
+        assert_eq!(
+            parse_cpuinfo(arm),
+            Some("Raspberry Pi 4 Model B Rev 1.4".into())
+        );
+
+        assert_eq!(parse_cpuinfo("processor\t: 0"), None);
+    }
+
+    #[test]
+    fn identifies_machines() {
+        let mut profile = get_mock_profile();
+        assert_eq!(
+            profile.id(),
+            "amd-ryzen-7-5800x-8-core-processor-16c-linux-x86_64"
+        );
+
+        profile.rustc = "rustc 1.92.0".into();
+        assert_eq!(
+            profile.id(),
+            "amd-ryzen-7-5800x-8-core-processor-16c-linux-x86_64"
+        );
+
+        profile.target_cpu = "target-cpu=native".into();
+        assert_eq!(
+            profile.id(),
+            "amd-ryzen-7-5800x-8-core-processor-16c-linux-x86_64-target-cpu-native"
+        );
+    }
+
+    #[test]
+    fn roundtrips_profiles() {
+        let profile = get_mock_profile();
+        let json = JsonValue::from(&profile);
+        assert_eq!(MachineProfile::try_from(&json), Ok(profile));
+    }
+}
@@ -1,68 +1,315 @@
-use std::{env, fs};
+use std::{env, fs, io};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
 pub mod runner;
 
+pub use context::*;
 pub use day::*;
+pub use deltas::{DEFAULT_TOLERANCE, parse_tolerance};
+pub use input::{InputError, InputSource};
+pub use parse_error::ParseError;
+pub use puzzle::*;
+pub use registry::*;
+pub use spans::Span;
+pub use year::*;
 
+mod allocations;
+mod answers;
+mod context;
 mod day;
+mod deltas;
+mod examples;
+mod heap;
+mod history;
+mod input;
+mod machine;
+mod panics;
+mod parse_error;
+mod protocol;
+mod puzzle;
 mod readme_benchmarks;
+mod registry;
 mod run_multi;
+mod spans;
+mod stats;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+// NOTE: declared here instead of in `solution!` so that the `runner` binary, which hosts every day, only has one.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[cfg(all(feature = "count-alloc", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: allocations::CountingAlloc = allocations::CountingAlloc;
+
+#[cfg(all(feature = "count-alloc", feature = "dhat-heap"))]
+compile_error!(
+    "the features `count-alloc` and `dhat-heap` both install a global allocator, enable only one."
+);
+
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    try_read_file(folder, puzzle).unwrap_or_else(|e| {
+        let path = puzzle.data_path(folder, "txt");
+        panic!("could not open input file \"{}\": {e}", path.display())
+    })
+}
+
+/// Helper function that reads a text file to a string, returning an error if it can't be read.
+pub fn try_read_file(folder: &str, puzzle: PuzzleId) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    fs::read_to_string(cwd.join(puzzle.data_path(folder, "txt")))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(puzzle.year))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// Also registers the parts as `SOLUTION`, so the `runner` binary can run the day in-process.
+///
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternative implementations of a part can be registered as named variants, which are benchmarked
+/// against the main implementation by `cargo compare`:
+///
+/// ```ignore
+/// advent_of_code::solution!(2025, 9, variants = {
+///     2 => [part_two_custom, part_two_rstar],
+/// });
+/// ```
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// By default, the parts are `part_one` and `part_two`. Days can register any list of parts with `parts`, e.g. to
+/// time a visualisation alongside the answers. Parts from 3 on are extra parts, shown by their name. They are only
+/// submitted if mapped to a part of the puzzle with `as`, `cargo solve 9 --submit 3` submits the answer of
+/// `part_two_brute_force` as part two:
+///
+/// ```ignore
+/// advent_of_code::solution!(2025, 9, parts = {
+///     1 => part_one,
+///     2 => part_two,
+///     3 => part_two_brute_force as 2,
+///     4 => visualise,
+/// });
+/// ```
+///
+/// Parameters of the real input are declared with `params`. Parts that take a [`Context`] as second argument can
+/// read them, example cases declare their own values in the examples manifest:
+///
+/// ```ignore
+/// advent_of_code::solution!(2025, 8, params = { edges = 1000 });
+///
+/// pub fn part_one(input: &str, ctx: &Context) -> Option<u64> {
+///     let edges: usize = ctx.param("edges")?;
+///     // ...
+/// }
+/// ```
+///
+/// Parts that consume or mutate their input get it from a `setup` function, which runs before every execution of a
+/// part and is excluded from its timing. The setup is timed on its own:
+///
+/// ```ignore
+/// advent_of_code::solution!(2025, 4, setup = parse_grid);
+///
+/// pub fn parse_grid(input: &str) -> Grid { .. }
+/// pub fn part_two(mut grid: Grid) -> Option<u64> { .. }
+/// ```
+///
+/// The puzzle input can be embedded at compile time with `input`, e.g. to ship a day as a single binary. It replaces
+/// `data/<year>/inputs/<day>.txt`, `--input` and `--example` still take precedence:
+///
+/// ```ignore
+/// advent_of_code::solution!(2025, 1, input = include_str!("../../data/2025/inputs/01.txt"));
+/// ```
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, { [part_one, 1, []] }, {}, {}, {}, {});
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, { [part_two, 2, []] }, {}, {}, {}, {});
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (
+        $year:expr, $day:expr,
+        parts = { $( $part:literal => $func:ident $(as $submit:literal)? ),* $(,)? }
+        $(, variants = { $( $vpart:literal => [$( $vfunc:ident ),* $(,)?] ),* $(,)? })?
+        $(, params = { $( $pname:ident = $pvalue:expr ),* $(,)? })?
+        $(, setup = $setup:ident)?
+        $(, input = $input:expr)?
+        $(,)?
+    ) => {
+        $crate::solution!(
+            @impl $year, $day,
+            { $( [$func, $part, [$($submit)?]] )* },
+            { $($( $vpart => [$( $vfunc ),*] ),*)? },
+            { $($( $pname = $pvalue ),*)? },
+            { $($setup)? },
+            { $($input)? }
+        );
+    };
+    (
+        $year:expr, $day:expr
+        $(, variants = { $( $vpart:literal => [$( $vfunc:ident ),* $(,)?] ),* $(,)? })?
+        $(, params = { $( $pname:ident = $pvalue:expr ),* $(,)? })?
+        $(, setup = $setup:ident)?
+        $(, input = $input:expr)?
+        $(,)?
+    ) => {
+        $crate::solution!(
+            @impl $year, $day,
+            { [part_one, 1, []] [part_two, 2, []] },
+            { $($( $vpart => [$( $vfunc ),*] ),*)? },
+            { $($( $pname = $pvalue ),*)? },
+            { $($setup)? },
+            { $($input)? }
+        );
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    // NOTE: the setup is passed on as a single token tree, so that it can be used inside the repetitions over the parts.
+    (@run {}, $func:expr, $($args:expr),*) => {
+        $crate::template::runner::run_part($func, $($args),*)
+    };
+    (@run { $setup:ident }, $func:expr, $($args:expr),*) => {
+        $crate::template::runner::run_part_with_setup($setup, $func, $($args),*)
+    };
+    (@measure {}, $func:expr, $input:expr, $ctx:expr) => {
+        $crate::template::runner::measure($func, $input, $ctx)
+    };
+    (@measure { $setup:ident }, $func:expr, $input:expr, $ctx:expr) => {
+        $crate::template::runner::measure_with_setup($setup, $func, $input, $ctx)
+    };
+    (@answer {}, $func:expr, $input:expr, $ctx:expr) => {
+        $crate::template::PartFn::call(&$func, $input, $ctx)
+    };
+    (@answer { $setup:ident }, $func:expr, $input:expr, $ctx:expr) => {
+        $crate::template::SetupPartFn::call_with(
+            &$func,
+            $crate::template::SetupFn::setup(&$setup, $input, $ctx),
+            $ctx,
+        )
+    };
+
+    (@input {}) => {
+        None
+    };
+    (@input { $input:expr }) => {
+        Some($input)
+    };
+
+    (@impl $year:expr, $day:expr, { $( [$func:expr, $part:expr, [$($submit:literal)?]] )* }, { $( $vpart:literal => [$( $vfunc:ident ),*] ),* }, { $( $pname:ident = $pvalue:expr ),* }, $setup:tt, $input:tt) => {
+        /// The year of the current puzzle.
+        #[allow(dead_code)]
+        const YEAR: $crate::template::Year = $crate::year!($year);
+
         /// The current day.
+        #[allow(dead_code)]
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = match $crate::template::PuzzleId::new(YEAR, DAY) {
+            Some(puzzle) => puzzle,
+            None => panic!("day is not part of the calendar of the year"),
+        };
+
+        /// Parameters of the real input.
+        fn input_params() -> $crate::template::Context {
+            $crate::template::Context::default()
+                $( .with(stringify!($pname), $pvalue) )*
+        }
+
+        /// The puzzle input, if it is embedded with `input`.
+        const INPUT: Option<&'static str> = $crate::solution!(@input $input);
+
+        /// Registry entry of the current day, used by the `runner` binary to run it in-process.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::Solution = $crate::template::Solution {
+            puzzle: PUZZLE,
+            params: input_params,
+            input: INPUT,
+            parts: &[$(
+                $crate::template::SolutionPart {
+                    id: $crate::template::PartId::new($part, stringify!($func), &[$($submit)?]),
+                    run: |input, ctx| {
+                        let id = $crate::template::PartId::new($part, stringify!($func), &[$($submit)?]);
+                        $crate::solution!(@run $setup, $func, input, ctx, PUZZLE, id)
+                    },
+                },
+            )*],
+        };
+
+        /// Every implementation of each part, the main implementation first. Benchmarked against each other by `--compare`.
+        #[allow(dead_code)]
+        const VARIANTS: &[$crate::template::Variant] = &[
+            $(
+                $crate::template::Variant {
+                    part: $part,
+                    name: stringify!($func),
+                    measure: |input, ctx| $crate::solution!(@measure $setup, $func, input, ctx),
+                },
+            )*
+            $($(
+                $crate::template::Variant {
+                    part: $vpart,
+                    name: stringify!($vfunc),
+                    measure: |input, ctx| $crate::solution!(@measure $setup, $vfunc, input, ctx),
+                },
+            )*)*
+        ];
+
+        /// Tests for the cases of the examples manifest of the day, generated by the build script.
+        #[cfg(test)]
+        mod example_cases {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[allow(dead_code)]
+            const PARTS: &[$crate::template::ExamplePart] = &[$(
+                $crate::template::ExamplePart {
+                    part: $part,
+                    answer: |input, ctx| {
+                        $crate::solution!(@answer $setup, $func, input, ctx)
+                            .map(|answer| answer.map(|answer| answer.to_string()))
+                    },
+                },
+            )*];
+
+            include!(concat!(env!("OUT_DIR"), "/examples/", $year, "_", $day, ".rs"));
+        }
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let (input, ctx) = read_input(PUZZLE, input_params(), INPUT);
+
+            if std::env::args().any(|x| x == "--compare") {
+                if !compare_variants(VARIANTS, &input, &ctx, PUZZLE) {
+                    std::process::exit(1);
+                }
+                return;
+            }
+
+            $({
+                let id = $crate::template::PartId::new($part, stringify!($func), &[$($submit)?]);
+                if id.runs_for(PUZZLE) {
+                    $crate::solution!(@run $setup, $func, &input, &ctx, PUZZLE, id);
+                }
+            })*
         }
     };
 }
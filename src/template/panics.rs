@@ -0,0 +1,106 @@
+/// Isolates panics of solution parts, so that a failing part doesn't abort the remaining parts and days.
+use std::{
+    any::Any,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::Mutex,
+};
+
+/// Location of the last panic, recorded by the hook installed in [`catch_panic`].
+/// Not thread-local, parts may panic on worker threads, e.g. with `rayon`.
+static LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+/// A caught panic of a part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartPanic {
+    pub message: String,
+    /// The source location of the panic, e.g. `src/bin/2025_08.rs:12:34`.
+    pub location: Option<String>,
+}
+
+impl fmt::Display for PartPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+/// Run a function and catch a panic, returning its message and location instead.
+/// The default panic output is suppressed while the function runs, the caller reports the panic.
+pub fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, PartPanic> {
+    let previous = panic::take_hook();
+
+    panic::set_hook(Box::new(|info| {
+        if let Ok(mut location) = LOCATION.lock() {
+            // NOTE: the runner includes the days by absolute path, report them relative to the repository.
+            *location = info.location().map(|l| {
+                let location = l.to_string();
+                location
+                    .strip_prefix(concat!(env!("CARGO_MANIFEST_DIR"), "/"))
+                    .map_or(location.clone(), ToString::to_string)
+            });
+        }
+    }));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    panic::set_hook(previous);
+
+    result.map_err(|payload| PartPanic {
+        message: payload_message(payload.as_ref()),
+        location: LOCATION
+            .lock()
+            .ok()
+            .and_then(|mut location| location.take()),
+    })
+}
+
+/// The message of a panic, panics with a formatted message carry a `String`, others a `&str`.
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".into()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartPanic, catch_panic};
+
+    #[test]
+    fn passes_results_through() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+    }
+
+    #[test]
+    fn catches_panics() {
+        let line = line!() + 1;
+        let panic = catch_panic(|| -> u32 { None.unwrap() }).unwrap_err();
+
+        assert_eq!(panic.message, "called `Option::unwrap()` on a `None` value");
+        assert!(panic.location.is_some_and(|location| {
+            location.starts_with(&format!("src/template/panics.rs:{line}:"))
+        }));
+
+        let panic = catch_panic(|| panic!("invalid junction `{}`", "1,2")).unwrap_err();
+        assert_eq!(panic.message, "invalid junction `1,2`");
+    }
+
+    #[test]
+    fn formats_panics() {
+        let panic = PartPanic {
+            message: "index out of bounds".into(),
+            location: Some("src/bin/2025_08.rs:12:34".into()),
+        };
+        assert_eq!(
+            panic.to_string(),
+            "panicked at src/bin/2025_08.rs:12:34: index out of bounds"
+        );
+    }
+}
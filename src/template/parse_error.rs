@@ -0,0 +1,118 @@
+/// A shared error type for input-format problems, for parts that return `Result<T, ParseError>`.
+use std::{
+    error::Error,
+    fmt,
+    num::{ParseFloatError, ParseIntError},
+};
+
+/// The input doesn't have the expected format.
+///
+/// ```ignore
+/// let (a, b) = line
+///     .split_once(',')
+///     .ok_or_else(|| ParseError::new("expected two comma-separated values").at_line(i + 1))?;
+/// ```
+#[derive(Debug)]
+pub struct ParseError {
+    message: String,
+    /// The 1-based line of the input, if known.
+    line: Option<usize>,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl ParseError {
+    pub fn new(message: impl fmt::Display) -> Self {
+        ParseError {
+            message: message.to_string(),
+            line: None,
+            source: None,
+        }
+    }
+
+    /// Attach the 1-based line of the input the problem is on.
+    #[must_use]
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Attach the error that caused the problem, e.g. a failed `str::parse`.
+    #[must_use]
+    pub fn caused_by(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    /// The alternate format, `{:#}`, appends the chain of causes.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message)?,
+            None => write!(f, "{}", self.message)?,
+        }
+
+        if f.alternate() {
+            let mut source = self.source();
+            while let Some(cause) = source {
+                write!(f, ": {cause}")?;
+                source = cause.source();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+impl From<ParseIntError> for ParseError {
+    fn from(e: ParseIntError) -> Self {
+        ParseError::new("invalid integer").caused_by(e)
+    }
+}
+
+impl From<ParseFloatError> for ParseError {
+    fn from(e: ParseFloatError) -> Self {
+        ParseError::new("invalid number").caused_by(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ParseError;
+
+    #[test]
+    fn formats_error_chains() {
+        let error = ParseError::new("invalid junction `1,x,3`")
+            .at_line(4)
+            .caused_by(ParseError::from("x".parse::<u32>().unwrap_err()));
+
+        assert_eq!(error.to_string(), "line 4: invalid junction `1,x,3`");
+        assert_eq!(
+            format!("{error:#}"),
+            "line 4: invalid junction `1,x,3`: invalid integer: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn converts_with_question_mark() {
+        fn parse(value: &str) -> Result<u64, ParseError> {
+            Ok(value.trim().parse::<u64>()?)
+        }
+
+        assert_eq!(parse(" 42").unwrap(), 42);
+        assert_eq!(
+            format!("{:#}", parse("-1").unwrap_err()),
+            "invalid integer: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+/// Machine-readable results of solution parts.
+/// When a solution is run with `--json`, every part emits a single JSON line on stdout that the
+/// orchestrator (`all`, `time`) consumes instead of parsing the human-readable output.
+use std::{collections::HashMap, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    PuzzleId, part_label,
+    stats::{BenchStats, stats_from_json, stats_to_json},
+};
+
+/// The result of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub puzzle: PuzzleId,
+    /// The part number, `None` if the day is not solved or failed before any part could run.
+    pub part: Option<u8>,
+    /// The name of the function implementing the part, `None` in reports of revisions that predate named parts.
+    pub name: Option<String>,
+    pub answer: Option<String>,
+    /// The execution time, the mean of all samples if the part was benched.
+    pub nanos: u128,
+    pub samples: u128,
+    /// The distribution of samples, only present if the part was benched.
+    pub stats: Option<BenchStats>,
+    /// The execution time of the setup of the part, excluded from `nanos`. Only present for days with a `setup`.
+    pub setup_nanos: Option<u64>,
+    /// Allocations inside the benchmark loop after warm-up, only reported with `--assert-no-alloc`.
+    pub bench_allocs: Option<u64>,
+    /// Why the day or part failed. A part without an answer, but with an error, panicked or returned an error.
+    /// A part with an answer and an error allocated inside the benchmark loop, see `bench_allocs`.
+    pub error: Option<String>,
+}
+
+impl PartReport {
+    /// Whether the part panicked or returned an error, as opposed to returning no answer.
+    pub fn is_errored(&self) -> bool {
+        self.part.is_some() && self.answer.is_none() && self.error.is_some()
+    }
+
+    /// Whether the part allocated inside the benchmark loop while `--assert-no-alloc` was set.
+    pub fn is_alloc_violation(&self) -> bool {
+        self.bench_allocs.is_some_and(|allocs| allocs > 0)
+    }
+
+    /// How the part is shown in output, e.g. `Part 1` or the name of an extra part.
+    pub fn label(&self) -> Option<String> {
+        self.part.map(|part| part_label(part, self.name.as_deref()))
+    }
+}
+
+impl PartReport {
+    /// Serialize the report as a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("part report only contains finite numbers")
+    }
+
+    /// Parse a line of output, returns `None` if the line is not a part report.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        JsonValue::from_str(line)
+            .ok()
+            .and_then(|json| Self::try_from(&json).ok())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn string_or_null(value: Option<&String>) -> JsonValue {
+    value.map_or(JsonValue::Null, |x| JsonValue::String(x.clone()))
+}
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("puzzle".into(), JsonValue::String(value.puzzle.to_string()));
+        map.insert(
+            "part".into(),
+            value
+                .part
+                .map_or(JsonValue::Null, |x| JsonValue::Number(f64::from(x))),
+        );
+        map.insert("name".into(), string_or_null(value.name.as_ref()));
+        map.insert("answer".into(), string_or_null(value.answer.as_ref()));
+        map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("stats".into(), stats_to_json(value.stats.as_ref()));
+        map.insert(
+            "setup_nanos".into(),
+            value
+                .setup_nanos
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+        map.insert(
+            "bench_allocs".into(),
+            value
+                .bench_allocs
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+        map.insert("error".into(), string_or_null(value.error.as_ref()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let puzzle = json
+            .get("puzzle")
+            .and_then(|v| v.get::<String>())
+            .and_then(|puzzle| PuzzleId::from_str(puzzle).ok())
+            .ok_or("Expected report.puzzle to be a puzzle id.")?;
+
+        let part = json
+            .get("part")
+            .map(|v| v.get::<f64>().map(|x| *x as u8))
+            .ok_or("Expected report.part to be null or a number.")?;
+
+        // NOTE: optional, revisions benched with `--baseline` may predate named parts.
+        let name = json.get("name").and_then(|v| v.get::<String>().cloned());
+
+        let answer = json
+            .get("answer")
+            .map(|v| v.get::<String>().cloned())
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u128)
+            .ok_or("Expected report.nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u128)
+            .ok_or("Expected report.samples to be a number.")?;
+
+        let stats = stats_from_json(json.get("stats"))?;
+
+        // NOTE: optional, revisions benched with `--baseline` may predate setups.
+        let setup_nanos = json
+            .get("setup_nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64);
+
+        // NOTE: optional, only reported with `--assert-no-alloc`.
+        let bench_allocs = json
+            .get("bench_allocs")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64);
+
+        let error = json
+            .get("error")
+            .map(|v| v.get::<String>().cloned())
+            .ok_or("Expected report.error to be null or string.")?;
+
+        Ok(PartReport {
+            puzzle,
+            part,
+            name,
+            answer,
+            nanos,
+            samples,
+            stats,
+            setup_nanos,
+            bench_allocs,
+            error,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PartReport;
+    use crate::{
+        day,
+        template::{PuzzleId, stats::BenchStats},
+        year,
+    };
+
+    fn get_mock_report(answer: &str) -> PartReport {
+        PartReport {
+            puzzle: PuzzleId::new(year!(2025), day!(1)).unwrap(),
+            part: Some(2),
+            name: Some("part_two".into()),
+            answer: Some(answer.into()),
+            nanos: 74_130,
+            samples: 100_000,
+            stats: Some(BenchStats {
+                min: 70_000.0,
+                median: 74_000.5,
+                mean: 74_130.0,
+                stddev: 1_200.25,
+                p95: 78_000.0,
+                outliers: 3,
+            }),
+            setup_nanos: None,
+            bench_allocs: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = get_mock_report("42");
+        let line = report.to_json_line();
+        assert!(!line.contains('\n'));
+        assert!(line.contains("\"nanos\":74130"));
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn roundtrips_setup_times() {
+        let mut report = get_mock_report("42");
+        report.setup_nanos = Some(1_530);
+        let line = report.to_json_line();
+        assert!(line.contains("\"setup_nanos\":1530"));
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+
+        let line = r#"{ "puzzle": "2025/01", "part": 1, "answer": "42", "nanos": 10, "samples": 1, "stats": null, "error": null }"#;
+        assert_eq!(PartReport::from_json_line(line).unwrap().setup_nanos, None);
+    }
+
+    #[test]
+    fn roundtrips_alloc_violations() {
+        let mut report = get_mock_report("42");
+        report.bench_allocs = Some(3);
+        report.error =
+            Some("2025/01 Part 2 allocated 3 times (96 B) inside the benchmark loop.".into());
+        let line = report.to_json_line();
+        let parsed = PartReport::from_json_line(&line).unwrap();
+        assert_eq!(parsed, report);
+        assert!(parsed.is_alloc_violation());
+        assert!(!parsed.is_errored());
+
+        report.bench_allocs = Some(0);
+        assert!(!report.is_alloc_violation());
+    }
+
+    #[test]
+    fn roundtrips_answers_with_patterns() {
+        let report = get_mock_report("@ ( ) ms (2s @ 5 samples)\n#..#\n.##.");
+        let line = report.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn roundtrips_errors() {
+        let report = PartReport {
+            puzzle: PuzzleId::new(year!(2025), day!(6)).unwrap(),
+            part: None,
+            name: None,
+            answer: None,
+            nanos: 0,
+            samples: 0,
+            stats: None,
+            setup_nanos: None,
+            bench_allocs: None,
+            error: Some("could not open input file".into()),
+        };
+        let line = report.to_json_line();
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn reports_returned_errors_as_errored() {
+        let mut report = get_mock_report("42");
+        report.answer = None;
+        report.error = Some("line 3: expected a number, got `x`".into());
+        assert!(report.is_errored());
+
+        report.error = None;
+        assert!(!report.is_errored());
+    }
+
+    #[test]
+    fn labels_extra_parts() {
+        let mut report = get_mock_report("42");
+        assert_eq!(report.label(), Some("Part 2".into()));
+
+        report.part = Some(3);
+        report.name = Some("part_two_brute_force".into());
+        assert_eq!(report.label(), Some("part_two_brute_force".into()));
+
+        let line = r#"{ "puzzle": "2025/01", "part": 1, "answer": "42", "nanos": 10, "samples": 1, "stats": null, "error": null }"#;
+        assert_eq!(PartReport::from_json_line(line).unwrap().name, None);
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(PartReport::from_json_line("Part 1: 0 (74.13ns)"), None);
+        assert_eq!(PartReport::from_json_line("{ not json"), None);
+        assert_eq!(
+            PartReport::from_json_line(r#"{ "puzzle": "2025/01" }"#),
+            None
+        );
+    }
+}
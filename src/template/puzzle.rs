@@ -0,0 +1,153 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+use crate::template::{Day, Year};
+
+#[cfg(feature = "today")]
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `<year>/<day>`, e.g. `2025/08`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    /// Creates a [`PuzzleId`] if `day` has a puzzle in the calendar of `year`, returns [`None`] otherwise.
+    pub const fn new(year: Year, day: Day) -> Option<Self> {
+        if !year.calendar().has_day(day) {
+            return None;
+        }
+        Some(Self { year, day })
+    }
+
+    /// Whether the puzzle has the given part, i.e. the final day of a year usually has no part two.
+    pub const fn has_part(&self, part: u8) -> bool {
+        self.year.calendar().has_part(self.day, part)
+    }
+
+    /// The name of the binary of the solution, e.g. `2025_08`.
+    pub fn bin_name(&self) -> String {
+        format!("{}_{}", self.year, self.day)
+    }
+
+    /// The path of the solution module, e.g. `src/bin/2025_08.rs`.
+    pub fn module_path(&self) -> String {
+        format!("src/bin/{}.rs", self.bin_name())
+    }
+
+    /// The path of a data file of the puzzle, e.g. `data/2025/inputs/08.txt`.
+    pub fn data_path(&self, folder: &str, extension: &str) -> PathBuf {
+        data_dir(self.year)
+            .join(folder)
+            .join(format!("{}.{extension}", self.day))
+    }
+}
+
+/// The folder all data of a year lives in, e.g. `data/2025`.
+pub fn data_dir(year: Year) -> PathBuf {
+    PathBuf::from("data").join(year.to_string())
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the puzzle of today if one was released today, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        if today.month() != 12 {
+            return None;
+        }
+        Self::new(
+            Year::new(u16::try_from(today.year()).ok()?)?,
+            Day::new(u8::try_from(today.day()).ok()?)?,
+        )
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s.split_once('/').ok_or(PuzzleIdFromStrError)?;
+        Self::new(
+            year.parse().map_err(|_| PuzzleIdFromStrError)?,
+            day.parse().map_err(|_| PuzzleIdFromStrError)?,
+        )
+        .ok_or(PuzzleIdFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub struct PuzzleIdFromStrError;
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting a puzzle as <year>/<day> with a day of the year's calendar, e.g. 2025/08",
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::{day, year};
+    use std::path::PathBuf;
+
+    #[test]
+    fn roundtrips_puzzle_ids() {
+        let puzzle = PuzzleId::new(year!(2025), day!(8)).unwrap();
+        assert_eq!(puzzle.to_string(), "2025/08");
+        assert_eq!("2025/08".parse::<PuzzleId>().unwrap(), puzzle);
+        assert_eq!("2025/8".parse::<PuzzleId>().unwrap(), puzzle);
+        assert!("2014/08".parse::<PuzzleId>().is_err());
+        assert!("2025/26".parse::<PuzzleId>().is_err());
+        assert!("2025/13".parse::<PuzzleId>().is_err());
+        assert!("2024/13".parse::<PuzzleId>().is_ok());
+        assert!("08".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn respects_final_day_without_part_two() {
+        let puzzle = PuzzleId::new(year!(2025), day!(12)).unwrap();
+        assert!(puzzle.has_part(1));
+        assert!(!puzzle.has_part(2));
+        assert!(PuzzleId::new(year!(2025), day!(11)).unwrap().has_part(2));
+        assert!(!PuzzleId::new(year!(2024), day!(25)).unwrap().has_part(2));
+        assert!(PuzzleId::new(year!(2024), day!(12)).unwrap().has_part(2));
+    }
+
+    #[test]
+    fn builds_paths() {
+        let puzzle = PuzzleId::new(year!(2025), day!(8)).unwrap();
+        assert_eq!(puzzle.bin_name(), "2025_08");
+        assert_eq!(puzzle.module_path(), "src/bin/2025_08.rs");
+        assert_eq!(
+            puzzle.data_path("inputs", "txt"),
+            PathBuf::from("data/2025/inputs/08.txt")
+        );
+    }
+}
@@ -1,11 +1,17 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fmt, fs, io};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::heap::{HeapProfile, format_bytes};
+use crate::template::machine::{self, MachineProfile};
+use crate::template::stats::format_nanos;
+use crate::template::timings::{PartTiming, Timings};
+use crate::template::{PuzzleId, Year, part_label};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// Each year has its own table, delimited by a pair of these markers.
+fn marker(year: Year) -> String {
+    format!("<!--- benchmarking table {year} --->")
+}
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -14,6 +20,15 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -26,12 +41,12 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./{}", puzzle.module_path())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,77 +67,220 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Format a table cell for a part, including the spread of samples if it was benched and the time of its setup.
+fn format_cell(part: &PartTiming) -> String {
+    let setup = part.setup_nanos.map_or(String::new(), |nanos| {
+        format!(" + `{}` setup", format_nanos(nanos))
+    });
+
+    match (part.formatted(), part.stats) {
+        (Some(timing), Some(stats)) => {
+            format!("`{timing} ± {}`{setup}", format_nanos(stats.stddev))
+        }
+        (Some(timing), None) => format!("`{timing}`{setup}"),
+        (None, _) if part.error.is_some() => "⚠ errored".into(),
+        (None, _) => "`-`".into(),
+    }
+}
+
+/// Format the heap cells of a day, the allocated bytes with the number of allocations and the peak heap.
+fn format_heap_cells(heap: Option<HeapProfile>) -> String {
+    match heap {
+        Some(heap) => format!(
+            " `{}` ({} allocs) | `{}` |",
+            format_bytes(heap.total_bytes),
+            heap.total_blocks,
+            format_bytes(heap.peak_bytes)
+        ),
+        None => " `-` | `-` |".into(),
+    }
+}
+
+/// The part columns of a table: `Part 1` and `Part 2`, followed by the extra parts of any day, ordered by part.
+/// Extra parts are matched by name, the same number may be a different part on another day.
+fn part_columns(timings: &Timings) -> Vec<(u8, String)> {
+    let mut columns: Vec<(u8, String)> = vec![(1, part_label(1, None)), (2, part_label(2, None))];
+
+    for timing in timings.data.iter().flat_map(|timing| &timing.parts) {
+        let label = timing.label();
+        if !columns.iter().any(|(_, column)| *column == label) {
+            columns.push((timing.part, label));
+        }
+    }
+
+    columns.sort_by_key(|(part, _)| *part);
+    columns
+}
+
+/// The table of a single machine, headed by the machine and the toolchain its timings were benched with.
+fn construct_machine_table(machine: &MachineProfile, year: Year, timings: Timings) -> Vec<String> {
+    // heap columns are only shown once a day was profiled with `cargo solve <day> --dhat`.
+    let has_heap = timings.data.iter().any(|timing| timing.heap.is_some());
+    let total_millis = timings.total_millis();
+    let columns = part_columns(&timings);
+
+    let toolchain = if machine.target_cpu == "default" {
+        format!("Built with `{}`.", machine.rustc)
+    } else {
+        format!(
+            "Built with `{}` and `{}`.",
+            machine.rustc, machine.target_cpu
+        )
+    };
+
+    let mut header = vec!["Day".to_string()];
+    header.extend(columns.iter().map(|(_, label)| label.clone()));
+    if has_heap {
+        header.extend(["Allocated".into(), "Peak heap".into()]);
+    }
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("### {}", machine.label()),
+        String::new(),
+        toolchain,
+        String::new(),
+        format!("| {} |", header.join(" | ")),
+        format!("|{}", " :---: |".repeat(header.len())),
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let Some(puzzle) = PuzzleId::new(year, timing.day) else {
+            continue;
+        };
+
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|(part, label)| {
+                match timing.parts.iter().find(|t| t.label() == *label) {
+                    Some(t) => format_cell(t),
+                    // the final day of a year usually has no part two, leave its cell empty. Same for days without
+                    // an extra part.
+                    None if *part <= 2 && puzzle.has_part(*part) => "`-`".into(),
+                    None => String::new(),
+                }
+            })
+            .collect();
+
+        let heap = if has_heap {
+            format_heap_cells(timing.heap)
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} |{heap}",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            get_path_for_bin(puzzle),
+            cells.join(" | ")
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines
+}
+
+fn construct_table(prefix: &str, year: Year, machines: &[(MachineProfile, Timings)]) -> String {
+    let mut lines: Vec<String> = vec![marker(year), format!("{prefix} {year} Benchmarks")];
 
+    for (machine, timings) in machines {
+        lines.extend(construct_machine_table(machine, year, timings.clone()));
+    }
+
+    lines.push(marker(year));
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    machines: &[(MachineProfile, Timings)],
+) -> Result<(), Error> {
+    let positions = locate_table(s, &marker(year))?;
+    let table = construct_table("##", year, machines);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Update the benchmark table of `year` in the readme, with a table per machine.
+/// If `AOC_README_MACHINE` is set, only the timings of that machine are shown.
+pub fn update(year: Year, machines: &[(MachineProfile, Timings)]) -> Result<(), Error> {
+    let machines: Vec<(MachineProfile, Timings)> = match machine::readme_machine() {
+        Some(id) => {
+            let selected: Vec<_> = machines
+                .iter()
+                .filter(|(machine, _)| machine.id() == id)
+                .cloned()
+                .collect();
+
+            if selected.is_empty() {
+                return Err(Error::Parser(format!(
+                    "No timings of machine `{id}` stored for {year}."
+                )));
+            }
+
+            selected
+        }
+        None => machines.to_vec(),
+    };
+
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, &machines)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{marker, update_content};
+    use crate::{
+        day,
+        template::heap::HeapProfile,
+        template::machine::MachineProfile,
+        template::stats::BenchStats,
+        template::timings::{PartTiming, Timing, Timings},
+        year,
+    };
+
+    fn get_mock_marker() -> String {
+        marker(year!(2025))
+    }
+
+    fn get_mock_machine() -> MachineProfile {
+        MachineProfile {
+            cpu: "Apple M1 Pro".into(),
+            cores: 10,
+            os: "macos aarch64".into(),
+            rustc: "rustc 1.91.0-nightly (6c699a372 2025-09-05)".into(),
+            target_cpu: "default".into(),
+        }
+    }
+
+    fn get_mock_part(part: u8, millis: f64) -> PartTiming {
+        PartTiming {
+            part,
+            name: None,
+            nanos: Some(millis * 1_000_000.0),
+            stats: None,
+            setup_nanos: None,
+            error: None,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
+        let timing = |day, part_1, part_2| Timing {
+            parts: vec![get_mock_part(1, part_1), get_mock_part(2, part_2)],
+            total_nanos: (part_1 + part_2) * 1_000_000.0,
+            ..Timing::new(day)
+        };
+
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
-                },
+                timing(day!(1), 10.0, 20.0),
+                timing(day!(2), 30.0, 40.0),
+                timing(day!(4), 40.0, 50.0),
             ],
         }
     }
@@ -131,53 +289,222 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2025),
+            &[(get_mock_machine(), get_mock_timings())],
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("{0} {0} {0}", get_mock_marker());
+        update_content(
+            &mut s,
+            year!(2025),
+            &[(get_mock_machine(), get_mock_timings())],
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        let mut s = format!("foo\nbar\n{0}{0}\nbaz", get_mock_marker());
+        update_content(
+            &mut s,
+            year!(2025),
+            &[(get_mock_machine(), get_mock_timings())],
+        )
+        .unwrap();
+        assert_eq!(s.contains("## 2025 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        let mut s = format!("foo\nbar\n{0}{0}\nbaz", get_mock_marker());
+        update_content(
+            &mut s,
+            year!(2025),
+            &[(get_mock_machine(), get_mock_timings())],
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            year!(2025),
+            &[(get_mock_machine(), get_mock_timings())],
+        )
+        .unwrap();
+        assert_eq!(
+            s.matches(&get_mock_marker()).collect::<Vec<&str>>().len(),
+            2
+        );
+        assert_eq!(
+            s.matches("## 2025 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn only_updates_table_of_year() {
+        let other = marker(year!(2024));
+        let mut s = format!("{other}\nold\n{other}\n{0}{0}", get_mock_marker());
+        update_content(
+            &mut s,
+            year!(2025),
+            &[(get_mock_machine(), get_mock_timings())],
+        )
+        .unwrap();
+        assert!(s.starts_with(&format!("{other}\nold\n{other}\n")));
+        assert!(s.contains("## 2025 Benchmarks"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_stats() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parts[0].stats = Some(BenchStats {
+            min: 9_000_000.0,
+            median: 9_900_000.0,
+            mean: 10_000_000.0,
+            stddev: 250_000.0,
+            p95: 11_000_000.0,
+            outliers: 0,
+        });
+        let mut s = format!("foo\nbar\n{0}\n{0}\nbaz", get_mock_marker());
+        update_content(&mut s, year!(2025), &[(get_mock_machine(), timings)]).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/2025_01.rs) | `10.0ms ± 250.0µs` | `20.0ms` |"));
+    }
+
+    #[test]
+    fn leaves_missing_part_two_of_final_day_empty() {
+        let mut timings = get_mock_timings();
+        timings.data[2].day = day!(12);
+        timings.data[2].parts.truncate(1);
+        let mut s = format!("{0}\n{0}", get_mock_marker());
+        update_content(&mut s, year!(2025), &[(get_mock_machine(), timings)]).unwrap();
+        assert!(s.contains("| [Day 12](./src/bin/2025_12.rs) | `40.0ms` |  |"));
+    }
+
+    #[test]
+    fn format_errored_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parts[0].nanos = None;
+        timings.data[1].parts[0].error = Some("panicked at src/bin/2025_02.rs:1:1: oops".into());
+        let mut s = format!("{0}\n{0}", get_mock_marker());
+        update_content(&mut s, year!(2025), &[(get_mock_machine(), timings)]).unwrap();
+        assert!(s.contains("| [Day 2](./src/bin/2025_02.rs) | ⚠ errored | `40.0ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_heap_profiles() {
+        let mut timings = get_mock_timings();
+        timings.data[0].heap = Some(HeapProfile {
+            total_bytes: 1536,
+            total_blocks: 3,
+            peak_bytes: 512,
+        });
+        let mut s = format!("{0}\n{0}", get_mock_marker());
+        update_content(&mut s, year!(2025), &[(get_mock_machine(), timings)]).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Allocated | Peak heap |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2025_01.rs) | `10.0ms` | `20.0ms` | `1.5 KiB` (3 allocs) | `512 B` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/2025_02.rs) | `30.0ms` | `40.0ms` | `-` | `-` |"));
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("foo\nbar\n{0}\n{0}\nbaz", get_mock_marker());
+        update_content(
+            &mut s,
+            year!(2025),
+            &[(get_mock_machine(), get_mock_timings())],
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2025 --->",
+            "## 2025 Benchmarks",
+            "",
+            "### Apple M1 Pro, 10 cores, macos aarch64",
+            "",
+            "Built with `rustc 1.91.0-nightly (6c699a372 2025-09-05)`.",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2025_01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2025_02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2025_04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2025 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_setup_times() {
+        let mut timings = get_mock_timings();
+        timings.data[2].parts[1].setup_nanos = Some(1_500.0);
+        let mut s = format!("{0}\n{0}", get_mock_marker());
+        update_content(&mut s, year!(2025), &[(get_mock_machine(), timings)]).unwrap();
+        assert!(
+            s.contains("| [Day 4](./src/bin/2025_04.rs) | `40.0ms` | `50.0ms` + `1.5µs` setup |")
+        );
+    }
+
+    #[test]
+    fn format_extra_parts() {
+        let mut timings = get_mock_timings();
+        let extra = |part, name: &str, millis| PartTiming {
+            name: Some(name.into()),
+            ..get_mock_part(part, millis)
+        };
+        timings.data[0].parts.push(extra(3, "visualise", 5.0));
+        timings.data[2]
+            .parts
+            .push(extra(3, "part_two_brute_force", 900.0));
+
+        let mut s = format!("{0}\n{0}", get_mock_marker());
+        update_content(&mut s, year!(2025), &[(get_mock_machine(), timings)]).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | visualise | part_two_brute_force |"));
+        assert!(s.contains("| :---: | :---: | :---: | :---: | :---: |"));
+        assert!(s.contains("| [Day 1](./src/bin/2025_01.rs) | `10.0ms` | `20.0ms` | `5.0ms` |  |"));
+        assert!(s.contains("| [Day 2](./src/bin/2025_02.rs) | `30.0ms` | `40.0ms` |  |  |"));
+        assert!(
+            s.contains("| [Day 4](./src/bin/2025_04.rs) | `40.0ms` | `50.0ms` |  | `900.0ms` |")
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_per_machine() {
+        let mut native = get_mock_machine();
+        native.target_cpu = "target-cpu=native".into();
+        let mut other = get_mock_timings();
+        other.data.truncate(1);
+
+        let mut s = format!("{0}\n{0}", get_mock_marker());
+        update_content(
+            &mut s,
+            year!(2025),
+            &[(get_mock_machine(), get_mock_timings()), (native, other)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            s.matches("### Apple M1 Pro, 10 cores, macos aarch64")
+                .count(),
+            2
+        );
+        assert!(s.contains(
+            "Built with `rustc 1.91.0-nightly (6c699a372 2025-09-05)` and `target-cpu=native`."
+        ));
+        assert!(s.contains("**Total: 190.00ms**"));
+        assert!(s.contains("**Total: 30.00ms**"));
+        assert_eq!(s.matches("## 2025 Benchmarks").count(), 1);
+    }
 }
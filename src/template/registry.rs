@@ -0,0 +1,133 @@
+/// Registry of solutions that can be run in-process by the `runner` binary.
+use crate::template::runner::Measurement;
+use crate::template::{Context, PuzzleId};
+
+/// Identifies a part of a day. Parts 1 and 2 are the parts of the puzzle, days can register extra parts from 3 on,
+/// e.g. a visualisation or a brute-force check of part two.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartId {
+    pub part: u8,
+    /// The name of the function implementing the part, e.g. `part_two_brute_force`.
+    pub name: &'static str,
+    /// The part of the puzzle the answer is submitted as, `None` if the part isn't submitted.
+    pub submit: Option<u8>,
+}
+
+impl PartId {
+    /// Parts of the puzzle are submitted as themselves, unless mapped to another part with `submit`.
+    /// Extra parts are only submitted if mapped.
+    pub const fn new(part: u8, name: &'static str, submit: &[u8]) -> Self {
+        let submit = match submit {
+            [submit] => Some(*submit),
+            _ if part <= 2 => Some(part),
+            _ => None,
+        };
+
+        Self { part, name, submit }
+    }
+
+    /// Whether the part runs for `puzzle`. Extra parts always run, the final day of a year may not have a part two.
+    pub const fn runs_for(&self, puzzle: PuzzleId) -> bool {
+        self.part > 2 || puzzle.has_part(self.part)
+    }
+
+    pub fn label(&self) -> String {
+        part_label(self.part, Some(self.name))
+    }
+}
+
+/// How a part is shown in output and in the readme: `Part 1` and `Part 2` for the parts of the puzzle,
+/// the name of the function for extra parts.
+pub fn part_label(part: u8, name: Option<&str>) -> String {
+    match name {
+        Some(name) if part > 2 => name.to_string(),
+        _ => format!("Part {part}"),
+    }
+}
+
+/// A single part of a solution.
+/// The part function is type-erased so that days with different answer types can share a registry.
+#[derive(Clone, Copy)]
+pub struct SolutionPart {
+    pub id: PartId,
+    pub run: fn(&str, &Context),
+}
+
+/// A named implementation of a part, benchmarked against the other implementations of the same part by `cargo compare`.
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub part: u8,
+    pub name: &'static str,
+    pub measure: fn(&str, &Context) -> Measurement,
+}
+
+/// A part that returns its answer as a string, checked against the expected answers of the examples manifest.
+#[derive(Clone, Copy)]
+pub struct ExamplePart {
+    pub part: u8,
+    /// The answer, or the formatted error chain if the part returned an error.
+    pub answer: fn(&str, &Context) -> Result<Option<String>, String>,
+}
+
+/// All registered parts of a day. The [`solution!`](crate::solution) macro creates one as `SOLUTION`.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Parameters of the real input.
+    pub params: fn() -> Context,
+    /// The puzzle input if the day embeds it with `input`, `None` to read it from the data folder.
+    pub input: Option<&'static str>,
+    pub parts: &'static [SolutionPart],
+}
+
+/// A set of solutions, looked up by puzzle.
+#[derive(Clone, Copy)]
+pub struct Registry {
+    solutions: &'static [Solution],
+}
+
+impl Registry {
+    pub const fn new(solutions: &'static [Solution]) -> Self {
+        Self { solutions }
+    }
+
+    /// Returns the solution registered for `puzzle`, if any.
+    pub fn get(&self, puzzle: PuzzleId) -> Option<&'static Solution> {
+        self.solutions
+            .iter()
+            .find(|solution| solution.puzzle == puzzle)
+    }
+
+    /// Iterates over all registered solutions.
+    pub fn iter(&self) -> impl Iterator<Item = &'static Solution> {
+        self.solutions.iter()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PartId;
+    use crate::{day, template::PuzzleId, year};
+
+    #[test]
+    fn maps_submissions() {
+        assert_eq!(PartId::new(2, "part_two", &[]).submit, Some(2));
+        assert_eq!(PartId::new(3, "visualise", &[]).submit, None);
+        assert_eq!(PartId::new(3, "part_two_brute_force", &[2]).submit, Some(2));
+    }
+
+    #[test]
+    fn labels_parts() {
+        assert_eq!(PartId::new(1, "part_one", &[]).label(), "Part 1");
+        assert_eq!(PartId::new(3, "visualise", &[]).label(), "visualise");
+    }
+
+    #[test]
+    fn runs_extra_parts_on_the_final_day() {
+        let puzzle = PuzzleId::new(year!(2025), day!(12)).unwrap();
+        assert!(!PartId::new(2, "part_two", &[]).runs_for(puzzle));
+        assert!(PartId::new(3, "finale", &[]).runs_for(puzzle));
+    }
+}
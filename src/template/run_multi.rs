@@ -1,56 +1,176 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fmt::Display, io, path::Path, process::ExitStatus};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, PuzzleId, Year, allocations::AllocMode,
+    protocol::PartReport,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// The outcome of running multiple days.
+pub struct MultiRun {
+    /// Benchmark times, only present if the days were timed.
+    pub timings: Option<Timings>,
+    /// The reports of all parts that were run.
+    pub reports: Vec<PartReport>,
+    /// Why the runner failed, e.g. because it didn't build or crashed.
+    pub failure: Option<String>,
+    /// Requested days the runner didn't report, because it failed before reaching them.
+    pub missing_days: Vec<Day>,
+}
 
-    let mut need_space = false;
+impl MultiRun {
+    /// Whether the runner succeeded and reported every requested day.
+    pub fn is_complete(&self) -> bool {
+        self.failure.is_none() && self.missing_days.is_empty()
+    }
+}
+
+/// Run the solutions of `days_to_run` of `year` in a single invocation of the runner.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    alloc: AllocMode,
+) -> MultiRun {
+    run_multi_in(None, year, days_to_run, is_release, is_timed, alloc)
+}
+
+/// Like [`run_multi`], but builds and runs the solutions of the checkout in `dir`, e.g. a worktree of another revision.
+pub fn run_multi_in(
+    dir: Option<&Path>,
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    alloc: AllocMode,
+) -> MultiRun {
+    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut reports = vec![];
+    let mut failure = None;
+    let mut missing_days = vec![];
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+        .collect();
+
+    if !days.is_empty() {
+        let puzzles: Vec<PuzzleId> = days
+            .iter()
+            .filter_map(|day| PuzzleId::new(year, *day))
+            .collect();
+        reports = match child_commands::run_solutions(dir, &puzzles, is_timed, is_release, alloc) {
+            Ok(reports) => reports,
+            Err(Error::Failed { status, reports }) => {
+                failure = Some(format!("the runner failed ({status})"));
+                reports
             }
-            need_space = true;
+            Err(e) => {
+                failure = Some(format!("failed to run the runner: {e}"));
+                vec![]
+            }
+        };
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        // NOTE: the runner reports every day it reaches, even if it is not solved.
+        missing_days = days
+            .iter()
+            .filter(|day| !reports.iter().any(|report| report.puzzle.day == **day))
+            .copied()
+            .collect();
+
+        for day in days {
+            let day_reports: Vec<_> = reports
+                .iter()
+                .filter(|report| report.puzzle.day == day && report.part.is_some())
+                .collect();
+
+            if !day_reports.is_empty() {
+                timings.push(child_commands::collect_timing(&day_reports, day));
+            }
+        }
+    }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    print_errors(&reports);
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    print_failure(failure.as_deref(), &missing_days);
 
-    if is_timed {
+    let timings = is_timed.then(|| {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+        timings
+    });
+
+    MultiRun {
+        timings,
+        reports,
+        failure,
+        missing_days,
+    }
+}
+
+/// Point out a failure of the runner, so that a partial run doesn't pass as a complete one.
+fn print_failure(failure: Option<&str>, missing_days: &[Day]) {
+    if let Some(failure) = failure {
+        eprintln!("\n✖ The run is incomplete: {failure}.");
+    }
+
+    if !missing_days.is_empty() {
+        let days: Vec<String> = missing_days.iter().map(ToString::to_string).collect();
+        eprintln!("✖ No results for day(s) {}.", days.join(", "));
+    }
+}
+
+/// Summarize the days and parts that errored, so that they don't pass as unsolved.
+fn print_errors(reports: &[PartReport]) {
+    let errors: Vec<&PartReport> = reports
+        .iter()
+        .filter(|report| report.part.is_none() || report.is_errored())
+        .filter(|report| report.error.is_some())
+        .collect();
+
+    if errors.is_empty() {
+        return;
+    }
+
+    println!("\n{ANSI_BOLD}Errors{ANSI_RESET}");
+    println!("------");
+
+    for report in errors {
+        let error = report.error.as_deref().unwrap_or_default();
+        match report.label() {
+            Some(label) => println!("Day {}, {label}: ⚠ {error}", report.puzzle.day),
+            None => println!("Day {}: ⚠ {error}", report.puzzle.day),
+        }
     }
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    /// The runner exited with an error, e.g. because it failed to build or crashed. Holds the reports emitted before.
+    Failed {
+        status: ExitStatus,
+        reports: Vec<PartReport>,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "lost the output of the runner"),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Failed { status, .. } => write!(f, "the runner failed ({status})"),
+        }
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -59,56 +179,74 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// All solutions are hosted by the `runner` binary.
+/// This module encapsulates interaction with this binary, both invoking it as well as collecting the part reports it emits.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        Day, PuzzleId, allocations::AllocMode, protocol::PartReport, timings::PartTiming,
+    };
     use std::{
+        env,
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Run the solutions of the given puzzles in a single invocation of the runner.
+    /// `dir` is the checkout to build the runner in, the current directory if `None`.
+    pub fn run_solutions(
+        dir: Option<&Path>,
+        puzzles: &[PuzzleId],
+        is_timed: bool,
+        is_release: bool,
+        alloc: AllocMode,
+    ) -> Result<Vec<PartReport>, Error> {
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".into(),
+            "--bin".into(),
+            "runner".into(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
+        args.extend(alloc.cargo_args().iter().map(ToString::to_string));
+
+        args.push("--".into());
+        args.push("--json".into());
+        args.extend(alloc.solution_args().iter().map(ToString::to_string));
+
         if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            // mirror `--time` flag to the runner.
+            args.push("--time".into());
         }
 
+        args.extend(puzzles.iter().map(PuzzleId::to_string));
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing part reports.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args);
+
+        if let Some(dir) = dir {
+            // NOTE: other checkouts share a target directory, so that benching them again only rebuilds what changed.
+            cmd.current_dir(dir).env(
+                "CARGO_TARGET_DIR",
+                env::current_dir()?.join("target/checkouts"),
+            );
+        }
+
+        let mut cmd = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut reports = vec![];
 
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
@@ -118,75 +256,56 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            match PartReport::from_json_line(&line) {
+                Some(report) => reports.push(report),
+                None => println!("{line}"),
+            }
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
-    }
+        if !status.success() {
+            return Err(Error::Failed { status, reports });
+        }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+        Ok(reports)
+    }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
+    /// Collect the timing of a day from the reports of its parts.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn collect_timing(reports: &[&PartReport], day: Day) -> super::Timing {
+        let mut timings = super::Timing::new(day);
+
+        for report in reports {
+            let Some(part) = report.part else {
+                continue;
+            };
+
+            // parts without an answer have no meaningful timing.
+            let has_answer = report.answer.is_some();
+
+            timings.parts.push(PartTiming {
+                part,
+                name: report.name.clone(),
+                nanos: has_answer.then_some(report.nanos as f64),
+                stats: report.stats.filter(|_| has_answer),
+                setup_nanos: report
+                    .setup_nanos
+                    .filter(|_| has_answer)
+                    .map(|nanos| nanos as f64),
+                error: report.error.clone().filter(|_| report.is_errored()),
             });
 
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+            // NOTE: the total of a day includes the setups of its parts, they are part of the cost of solving it.
+            if has_answer {
+                timings.total_nanos +=
+                    report.nanos as f64 + report.setup_nanos.unwrap_or_default() as f64;
+            }
+        }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        timings.parts.sort_by_key(|timing| timing.part);
+        timings
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,53 +324,92 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::collect_timing;
+
+        use crate::{
+            day,
+            template::{PuzzleId, protocol::PartReport},
+            year,
+        };
 
-        use crate::day;
+        fn get_mock_report(part: u8, answer: Option<&str>, nanos: u128) -> PartReport {
+            PartReport {
+                puzzle: PuzzleId::new(year!(2025), day!(1)).unwrap(),
+                part: Some(part),
+                name: None,
+                answer: answer.map(Into::into),
+                nanos,
+                samples: 100,
+                stats: None,
+                setup_nanos: None,
+                bench_allocs: None,
+                error: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+        fn collects_execution_times() {
+            let part_1 = get_mock_report(1, Some("0"), 74);
+            let part_2 = get_mock_report(2, Some("10"), 74_130_000);
+            let res = collect_timing(&[&part_1, &part_2], day!(1));
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part(1).unwrap().formatted().unwrap(), "74.0ns");
+            assert_eq!(res.part(2).unwrap().formatted().unwrap(), "74.1ms");
+            assert_eq!(res.part_nanos(2), Some(74_130_000_f64));
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
+        fn collects_with_patterns_in_answer() {
+            let part_1 = get_mock_report(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2_000_000_000);
+            let part_2 = get_mock_report(2, Some("#..#\n.##."), 100_000_000);
+            let res = collect_timing(&[&part_1, &part_2], day!(1));
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part(1).unwrap().formatted().unwrap(), "2.0s");
+            assert_eq!(res.part(2).unwrap().formatted().unwrap(), "100.0ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
+        fn collects_errored_parts() {
+            let mut part_1 = get_mock_report(1, None, 0);
+            part_1.error = Some("panicked at src/bin/2025_01.rs:3:5: explicit panic".into());
+            let part_2 = get_mock_report(2, Some("10"), 100);
+            let res = collect_timing(&[&part_1, &part_2], day!(1));
+            assert_eq!(res.part_nanos(1), None);
+            assert_eq!(res.part_error(1), part_1.error.as_deref());
+            assert_eq!(res.part_error(2), None);
+            assert_approx_eq!(res.total_nanos, 100_f64);
+        }
+
+        #[test]
+        fn collects_missing_parts() {
+            let part_1 = get_mock_report(1, None, 10);
+            let part_2 = get_mock_report(2, None, 10);
+            let res = collect_timing(&[&part_1, &part_2], day!(1));
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_nanos(1).is_none(), true);
+            assert_eq!(res.part_nanos(2).is_none(), true);
+        }
+
+        #[test]
+        fn collects_setup_times() {
+            let mut part_2 = get_mock_report(2, Some("43"), 1_600);
+            part_2.setup_nanos = Some(1_500);
+            let res = collect_timing(&[&part_2], day!(4));
+            assert_eq!(res.part_nanos(2), Some(1_600_f64));
+            assert_eq!(res.part(2).unwrap().setup_nanos, Some(1_500_f64));
+            assert_approx_eq!(res.total_nanos, 3100_f64);
+        }
+
+        #[test]
+        fn collects_extra_parts() {
+            let mut part_3 = get_mock_report(3, Some("1"), 1_000);
+            part_3.name = Some("visualise".into());
+            let part_1 = get_mock_report(1, Some("0"), 10);
+            let res = collect_timing(&[&part_3, &part_1], day!(1));
+            assert_approx_eq!(res.total_nanos, 1010_f64);
+            assert_eq!(res.parts[0].part, 1);
+            assert_eq!(res.parts[1].label(), "visualise");
+            assert_eq!(res.part_nanos(3), Some(1_000_f64));
         }
     }
 }
@@ -2,99 +2,713 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
-use std::process::Output;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::allocations::{self, AllocStats};
+use crate::template::answers;
+use crate::template::context::parse_param;
+use crate::template::examples::ExampleManifest;
+use crate::template::heap::format_bytes;
+use crate::template::history::unix_timestamp;
+use crate::template::panics::{PartPanic, catch_panic};
+use crate::template::protocol::PartReport;
+use crate::template::spans::{self, SpanBreakdown};
+use crate::template::stats::{BenchStats, format_nanos};
+use crate::template::submissions::{Submission, SubmissionLog, Verdict};
+use crate::template::{
+    ANSI_ITALIC, ANSI_RESET, Context, ExamplePart, InputSource, PartFn, PartId, PuzzleId, Registry,
+    SetupFn, SetupPartFn, Variant, aoc_client,
+};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Run the registered solutions of `puzzles` in-process, in the given order.
+/// Puzzles without a registered solution are reported as not solved.
+pub fn run_days(registry: &Registry, puzzles: &[PuzzleId]) {
+    let mut need_space = false;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    for &puzzle in puzzles {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        print_day_header(puzzle);
+
+        let Some(solution) = registry.get(puzzle) else {
+            println!("Not solved.");
+            // NOTE: report the day anyway, so the orchestrator can tell it apart from a day the runner never reached.
+            emit_report(&day_report(puzzle, None));
+            continue;
+        };
+
+        match InputSource::Puzzle
+            .with_embedded(solution.input)
+            .load(puzzle)
+        {
+            Ok((input, _)) => {
+                let ctx = (solution.params)();
+
+                for part in solution
+                    .parts
+                    .iter()
+                    .filter(|part| part.id.runs_for(puzzle))
+                {
+                    (part.run)(&input, &ctx);
+                }
+            }
+            Err(e) => {
+                let error = e.to_string();
+                eprintln!("{error}");
+                emit_report(&day_report(puzzle, Some(error)));
+            }
+        }
+    }
+}
+
+/// The report of a day that did not run any part, with the error that stopped it or `None` if it is not solved.
+fn day_report(puzzle: PuzzleId, error: Option<String>) -> PartReport {
+    PartReport {
+        puzzle,
+        part: None,
+        name: None,
+        answer: None,
+        nanos: 0,
+        samples: 0,
+        stats: None,
+        setup_nanos: None,
+        bench_allocs: None,
+        error,
+    }
+}
+
+fn print_day_header(puzzle: PuzzleId) {
+    println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+    println!("------");
+}
+
+pub fn run_part<S, F>(func: F, input: &str, ctx: &Context, puzzle: PuzzleId, id: PartId)
+where
+    F: PartFn<S>,
+    F::Answer: Display,
+{
+    run_and_report(
+        || black_box(input),
+        |input| func.call(input, ctx),
+        false,
+        puzzle,
+        id,
+    );
+}
+
+/// Run a part of a day with a `setup`, see [`solution!`](crate::solution). Every execution of the part gets a fresh
+/// input from `setup`, only the part is timed. The setup is timed on its own and printed next to the part.
+pub fn run_part_with_setup<SS, S, Setup, F>(
+    setup: Setup,
+    func: F,
+    input: &str,
+    ctx: &Context,
+    puzzle: PuzzleId,
+    id: PartId,
+) where
+    Setup: SetupFn<SS>,
+    F: SetupPartFn<Setup::Input, S>,
+    F::Answer: Display,
+{
+    run_and_report(
+        || setup.setup(input, ctx),
+        |input| func.call_with(input, ctx),
+        true,
+        puzzle,
+        id,
+    );
+}
+
+/// Run a part, print its result and report it. See [`run_timed`] for `setup` and `batched`.
+fn run_and_report<I, T: Display>(
+    setup: impl Fn() -> I,
+    func: impl Fn(I) -> Result<Option<T>, String>,
+    batched: bool,
+    puzzle: PuzzleId,
+    id: PartId,
+) {
+    if !allocations::is_enabled() && env::args().any(|x| x == "--assert-no-alloc") {
+        eprintln!(
+            "`--assert-no-alloc` requires the counting allocator of the `count-alloc` feature."
+        );
+        process::exit(1);
+    }
+
+    let part = id.part;
+    let part_str = id.label();
+    let trace_path = trace_path();
+    let trace = trace_path.as_ref().map(|_| format!("{puzzle} {part_str}"));
+
+    // NOTE: a panicking part is reported as errored, the remaining parts and days still run.
+    let timed = match catch_panic(|| {
+        run_timed(setup, func, batched, part, trace.as_deref(), |result| {
+            print_result(result, &part_str, "")
+        })
+    }) {
+        Ok(timed) => timed,
+        Err(panic) => {
+            print_panic(&panic, &part_str);
+            emit_report(&PartReport {
+                puzzle,
+                part: Some(part),
+                name: Some(id.name.into()),
+                answer: None,
+                nanos: 0,
+                samples: 0,
+                stats: None,
+                setup_nanos: None,
+                bench_allocs: None,
+                error: Some(panic.to_string()),
+            });
+            return;
+        }
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(
+        &timed.result,
+        &part_str,
+        &format_duration(
+            &timed.duration,
+            timed.samples,
+            timed.setup.as_ref(),
+            timed.allocs.as_ref(),
+        ),
+    );
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(stats) = &timed.stats {
+        print_stats(stats);
+
+        if let Some(spans) = &timed.spans {
+            print_spans(spans, stats.mean);
+        }
+    }
+
+    if let Some(path) = &trace_path
+        && let Err(e) = spans::write_trace(path)
+    {
+        eprintln!("Failed to write trace to {}: {e}", path.display());
     }
+
+    let bench_allocs = timed
+        .bench_allocs
+        .filter(|_| env::args().any(|x| x == "--assert-no-alloc"));
+
+    let error = bench_allocs
+        .filter(|allocs| allocs.allocs > 0)
+        .map(|allocs| {
+            format!(
+                "{puzzle} {part_str} allocated {} times ({}) inside the benchmark loop after warm-up.",
+                allocs.allocs,
+                format_bytes(allocs.bytes)
+            )
+        });
+
+    if let Some(error) = &error {
+        eprintln!("{error}");
+    }
+
+    // NOTE: a part that returned an error is reported as errored, like a panicking part.
+    emit_report(&PartReport {
+        puzzle,
+        part: Some(part),
+        name: Some(id.name.into()),
+        answer: timed
+            .result
+            .as_ref()
+            .ok()
+            .and_then(Option::as_ref)
+            .map(ToString::to_string),
+        nanos: timed.duration.as_nanos(),
+        samples: timed.samples,
+        stats: timed.stats,
+        setup_nanos: timed
+            .setup
+            .and_then(|setup| u64::try_from(setup.as_nanos()).ok()),
+        bench_allocs: bench_allocs.map(|allocs| allocs.allocs),
+        // NOTE: a part that allocated has an answer, the caller decides whether the allocations fail the run.
+        error: error.or_else(|| timed.result.as_ref().err().cloned()),
+    });
+
+    if let Ok(Some(result)) = timed.result {
+        submit_result(result, puzzle, id);
+    }
+}
+
+/// Print a machine-readable report of a part if the solution was invoked with `--json`.
+fn emit_report(report: &PartReport) {
+    if env::args().any(|x| x == "--json") {
+        println!("{}", report.to_json_line());
+    }
+}
+
+/// The result of a part and how long it took to compute.
+struct Timed<T> {
+    result: T,
+    duration: Duration,
+    samples: u128,
+    /// The distribution of samples, only present if the part was benched.
+    stats: Option<BenchStats>,
+    /// Allocations of the first execution, only present with the `count-alloc` feature.
+    allocs: Option<AllocStats>,
+    /// Allocations inside the benchmark loop, only present with the `count-alloc` feature if the part was benched.
+    bench_allocs: Option<AllocStats>,
+    /// Spans inside the benchmark loop, only present if the part was benched and has spans.
+    spans: Option<SpanBreakdown>,
+    /// Duration of the setup, the mean of its samples if the part was benched. Only present for batched parts.
+    setup: Option<Duration>,
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
+///
+/// When benched, the returned duration is the mean of the samples.
+/// The input of every execution comes from `setup`, which is not timed. Parts that are not `batched` share one input,
+/// `setup` only hands it out. `batched` parts consume or mutate their input, they get a fresh one for every execution
+/// and the setup is timed on its own.
+/// With the `dhat-heap` feature, the heap profile of the first execution is written to `dhat-heap-<part>.json`.
+/// With `trace`, the spans of the first execution are added to the trace of the process, grouped by `trace`.
+#[cfg_attr(not(feature = "dhat-heap"), allow(unused_variables))]
+fn run_timed<I, T>(
+    setup: impl Fn() -> I,
     func: impl Fn(I) -> T,
-    input: I,
+    batched: bool,
+    part: u8,
+    trace: Option<&str>,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> Timed<T> {
+    let timer = Instant::now();
+    let input = setup();
+    let setup_time = timer.elapsed();
+
     let timer = Instant::now();
-    let result = {
+    let (result, allocs) = {
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = dhat::Profiler::builder()
+            .file_name(crate::template::heap::dhat_file_name(part))
+            .build();
 
-        func(input)
+        match trace {
+            Some(name) => spans::trace(name, || allocations::count(|| func(input))),
+            None => allocations::count(|| func(input)),
+        }
     };
     let base_time = timer.elapsed();
+    let allocs = allocations::is_enabled().then_some(allocs);
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if std::env::args().any(|x| x == "--time") {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+
+        let (stats, samples, bench_allocs, spans) = if batched {
+            bench_batched(&setup, &func, &base_time, &setup_time)
+        } else {
+            bench(|| func(setup()), &base_time)
+        };
+
+        let setup = batched.then(|| {
+            let (setup_stats, ..) = bench(&setup, &setup_time);
+            mean_duration(&setup_stats)
+        });
+
+        Timed {
+            result,
+            duration: mean_duration(&stats),
+            samples,
+            stats: Some(stats),
+            allocs,
+            bench_allocs: allocations::is_enabled().then_some(bench_allocs),
+            spans: (!spans.totals.is_empty()).then_some(spans),
+            setup,
+        }
     } else {
-        (base_time, 1)
-    };
+        Timed {
+            result,
+            duration: base_time,
+            samples: 1,
+            stats: None,
+            allocs,
+            bench_allocs: None,
+            spans: None,
+            setup: batched.then_some(setup_time),
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn mean_duration(stats: &BenchStats) -> Duration {
+    Duration::from_nanos(stats.mean.round() as u64)
+}
+
+/// Read the input of a day and its parameters from the source selected with `--input <path|->` or `--example <name>`,
+/// the personal puzzle input by default, which is read from `embedded` if the day embeds it. Parameters of example cases
+/// and `--param <name>=<value>` overrides are applied on top of the ones of the real input.
+pub fn read_input(
+    puzzle: PuzzleId,
+    input_params: Context,
+    embedded: Option<&'static str>,
+) -> (String, Context) {
+    let args: Vec<String> = env::args().collect();
+
+    let source = InputSource::from_args(&args)
+        .unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+        .with_embedded(embedded);
+
+    let (input, source_params) = source.load(puzzle).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let mut overrides = Context::default();
+    for (i, arg) in args.iter().enumerate() {
+        if arg != "--param" {
+            continue;
+        }
+
+        match args.get(i + 1).map(|param| parse_param(param)) {
+            Some(Ok((name, value))) => overrides = overrides.with_override(&name, value),
+            Some(Err(e)) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+            None => {
+                eprintln!(
+                    "Unexpected command-line input. Format: cargo solve 1 --param <name>=<value>"
+                );
+                process::exit(1);
+            }
+        }
+    }
+
+    (input, input_params.merge(&source_params).merge(&overrides))
+}
+
+/// Run a part against an example case and assert its expected answer.
+/// Called by the tests that are generated for every case of `data/<year>/examples/<day>.json`.
+pub fn check_example(
+    puzzle: PuzzleId,
+    parts: &[ExamplePart],
+    input_params: Context,
+    name: &str,
+    part: u8,
+) {
+    let manifest = ExampleManifest::read(puzzle).unwrap();
+    let case = manifest.get(name).unwrap();
+    let (input, example_params) = InputSource::Example(name.to_string()).load(puzzle).unwrap();
+    let ctx = input_params.merge(&example_params);
+
+    let example_part = parts
+        .iter()
+        .find(|example_part| example_part.part == part)
+        .unwrap_or_else(|| panic!("part {part} is not registered in `solution!`"));
 
-    (result, run.0, run.1)
+    let answer = (example_part.answer)(&input, &ctx)
+        .unwrap_or_else(|e| panic!("example `{name}`, part {part} returned an error: {e}"));
+
+    assert_eq!(
+        answer.as_deref(),
+        case.answers.get(&part).map(String::as_str),
+        "example `{name}`, part {part}"
+    );
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// The answer and benchmark of a single implementation of a part.
+#[derive(Clone, Debug)]
+pub struct Measurement {
+    pub answer: Option<String>,
+    pub stats: BenchStats,
+    pub samples: u128,
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Run a solution part once for its answer, then bench it. Used to compare implementations of a part.
+pub fn measure<S, F>(func: F, input: &str, ctx: &Context) -> Measurement
+where
+    F: PartFn<S>,
+    F::Answer: Display,
+{
+    let func = |input| func.call(input, ctx);
+
+    let timer = Instant::now();
+    let answer = func(input).ok().flatten().map(|answer| answer.to_string());
+    let base_time = timer.elapsed();
+
+    let (stats, samples, _, _) = bench(|| func(black_box(input)), &base_time);
+
+    Measurement {
+        answer,
+        stats,
+        samples,
+    }
+}
+
+/// Like [`measure`], for the parts of a day with a `setup`. Only the part is benched, with a fresh input per execution.
+pub fn measure_with_setup<SS, S, Setup, F>(
+    setup: Setup,
+    func: F,
+    input: &str,
+    ctx: &Context,
+) -> Measurement
+where
+    Setup: SetupFn<SS>,
+    F: SetupPartFn<Setup::Input, S>,
+    F::Answer: Display,
+{
+    let setup = || setup.setup(input, ctx);
+    let func = |input| func.call_with(input, ctx);
+
+    let timer = Instant::now();
+    let prepared = setup();
+    let setup_time = timer.elapsed();
+
+    let timer = Instant::now();
+    let answer = func(prepared)
+        .ok()
+        .flatten()
+        .map(|answer| answer.to_string());
+    let base_time = timer.elapsed();
+
+    let (stats, samples, _, _) = bench_batched(setup, func, &base_time, &setup_time);
+
+    Measurement {
+        answer,
+        stats,
+        samples,
+    }
+}
+
+/// Bench every variant of a day and print how it compares to the main implementation of its part,
+/// which is the first variant registered for a part.
+/// Returns `false` if any variant returned a different answer than the main implementation.
+pub fn compare_variants(
+    variants: &[Variant],
+    input: &str,
+    ctx: &Context,
+    puzzle: PuzzleId,
+) -> bool {
+    print_day_header(puzzle);
+
+    let mut parts: Vec<u8> = variants.iter().map(|variant| variant.part).collect();
+    parts.sort_unstable();
+    parts.dedup();
+
+    let name_width = variants
+        .iter()
+        .map(|variant| variant.name.len())
+        .max()
+        .unwrap_or_default();
+
+    let mut answers_match = true;
+
+    for part in parts {
+        println!("Part {part}");
+
+        let mut baseline: Option<Measurement> = None;
+
+        for variant in variants.iter().filter(|variant| variant.part == part) {
+            print!(
+                "  {:<name_width$}  > {ANSI_ITALIC}benching{ANSI_RESET}",
+                variant.name
+            );
+            let _ = stdout().flush();
+
+            let measurement = (variant.measure)(input, ctx);
+            let baseline = baseline.get_or_insert_with(|| measurement.clone());
+
+            let answer = measurement.answer.as_deref().unwrap_or("✖");
+            let mismatch = if measurement.answer == baseline.answer {
+                String::new()
+            } else {
+                answers_match = false;
+                format!(
+                    " {ANSI_BOLD}(expected {}){ANSI_RESET}",
+                    baseline.answer.as_deref().unwrap_or("✖")
+                )
+            };
+
+            print!("\r");
+            println!(
+                "  {:<name_width$}  {:>9} ± {:<9}  {:>6.2}×  {answer}{mismatch}",
+                variant.name,
+                format_nanos(measurement.stats.mean),
+                format_nanos(measurement.stats.stddev),
+                baseline.stats.mean / measurement.stats.mean,
+            );
+        }
+    }
+
+    if !answers_match {
+        eprintln!("Variants of {puzzle} returned different answers.");
+    }
+
+    answers_match
+}
+
+/// Minimum duration of a single sample. Faster functions are timed in batches,
+/// so that the overhead of `Instant::now()` doesn't dominate the measurement.
+const MIN_SAMPLE_DURATION: Duration = Duration::from_micros(10);
+
+/// Duration of the warm-up phase, which also yields a better estimate of the execution time than the first run.
+const WARMUP_DURATION: Duration = Duration::from_millis(100);
+
+/// Bench a function, returns the statistics of the samples, their number and the allocations made after warm-up.
+#[allow(clippy::cast_precision_loss)]
+fn bench<T>(
+    func: impl Fn() -> T,
+    base_time: &Duration,
+) -> (BenchStats, u128, AllocStats, SpanBreakdown) {
+    let warmup_iterations =
+        (WARMUP_DURATION.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(1, 10000);
+
+    let timer = Instant::now();
+    for _ in 0..warmup_iterations {
+        black_box(func());
+    }
+    let estimate = cmp::max(timer.elapsed().as_nanos() / warmup_iterations, 1);
+
+    let batch_size = cmp::max(MIN_SAMPLE_DURATION.as_nanos() / estimate, 1);
 
     let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+        (Duration::from_secs(1).as_nanos() / (estimate * batch_size)).clamp(10, 10000);
+
+    let mut samples: Vec<f64> = Vec::with_capacity(bench_iterations as usize);
 
-    let mut timers: Vec<Duration> = vec![];
+    let (((), allocs), totals) = spans::collect_totals(|| {
+        allocations::count(|| {
+            for _ in 0..bench_iterations {
+                let timer = Instant::now();
+                for _ in 0..batch_size {
+                    black_box(func());
+                }
+                samples.push(timer.elapsed().as_nanos() as f64 / batch_size as f64);
+            }
+        })
+    });
 
-    for _ in 0..bench_iterations {
+    (
+        BenchStats::from_samples(&mut samples),
+        bench_iterations,
+        allocs,
+        SpanBreakdown {
+            totals,
+            executions: bench_iterations * batch_size,
+        },
+    )
+}
+
+/// Bench a function that consumes its input, like [`bench`]. The inputs of a sample are made by `setup` before the
+/// sample is timed, their allocations and spans are not counted. `setup_time` is the duration of a single setup, the
+/// number of samples is chosen so that the setups and the function together take about a second.
+#[allow(clippy::cast_precision_loss)]
+fn bench_batched<I, T>(
+    setup: impl Fn() -> I,
+    func: impl Fn(I) -> T,
+    base_time: &Duration,
+    setup_time: &Duration,
+) -> (BenchStats, u128, AllocStats, SpanBreakdown) {
+    let warmup_iterations = (WARMUP_DURATION.as_nanos()
+        / cmp::max((*base_time + *setup_time).as_nanos(), 10))
+    .clamp(1, 10000);
+
+    let mut warmup = Duration::ZERO;
+    let warmup_timer = Instant::now();
+    for _ in 0..warmup_iterations {
+        let input = setup();
         let timer = Instant::now();
         black_box(func(black_box(input)));
-        timers.push(timer.elapsed());
+        warmup += timer.elapsed();
     }
+    let estimate = cmp::max(warmup.as_nanos() / warmup_iterations, 1);
+    let setup_estimate = (warmup_timer.elapsed() - warmup).as_nanos() / warmup_iterations;
+
+    let batch_size = cmp::max(MIN_SAMPLE_DURATION.as_nanos() / estimate, 1);
+
+    let bench_iterations = (Duration::from_secs(1).as_nanos()
+        / ((estimate + setup_estimate) * batch_size))
+        .clamp(10, 10000);
+
+    let mut samples: Vec<f64> = Vec::with_capacity(bench_iterations as usize);
+    let mut allocs = AllocStats::default();
+
+    let ((), totals) = spans::collect_totals(|| {
+        for _ in 0..bench_iterations {
+            let inputs: Vec<I> = spans::suspended(|| (0..batch_size).map(|_| setup()).collect());
+
+            let (elapsed, sample_allocs) = allocations::count(|| {
+                let timer = Instant::now();
+                for input in inputs {
+                    black_box(func(black_box(input)));
+                }
+                timer.elapsed()
+            });
+
+            samples.push(elapsed.as_nanos() as f64 / batch_size as f64);
+            allocs.allocs += sample_allocs.allocs;
+            allocs.bytes += sample_allocs.bytes;
+            allocs.peak_bytes = cmp::max(allocs.peak_bytes, sample_allocs.peak_bytes);
+        }
+    });
 
     (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
+        BenchStats::from_samples(&mut samples),
         bench_iterations,
+        allocs,
+        SpanBreakdown {
+            totals,
+            executions: bench_iterations * batch_size,
+        },
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
+fn format_duration(
+    duration: &Duration,
+    samples: u128,
+    setup: Option<&Duration>,
+    allocs: Option<&AllocStats>,
+) -> String {
+    let setup = setup.map_or(String::new(), |setup| format!(", setup {setup:.1?}"));
+    let allocs = allocs.map_or(String::new(), |allocs| {
+        format!(
+            ", {} allocs, {}, peak {}",
+            allocs.allocs,
+            format_bytes(allocs.bytes),
+            format_bytes(allocs.peak_bytes)
+        )
+    });
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
-        format!(" ({duration:.1?})")
+        format!(" ({duration:.1?}{setup}{allocs})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} @ {samples} samples{setup}{allocs})")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_stats(stats: &BenchStats) {
+    println!(
+        "  {ANSI_ITALIC}min {} · median {} · σ {} · p95 {} · {} outliers{ANSI_RESET}",
+        format_nanos(stats.min),
+        format_nanos(stats.median),
+        format_nanos(stats.stddev),
+        format_nanos(stats.p95),
+        stats.outliers
+    );
+}
+
+fn print_result<T: Display>(result: &Result<Option<T>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(Some(result)) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -114,7 +728,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,17 +736,45 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(error) => {
+            let str = format!("{part}: ⚠ {ANSI_ITALIC}{error}{ANSI_RESET}{duration_str}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+            }
+        }
     }
 }
 
+/// Print the average time of each span of a part, `part_nanos` is the mean time of the part.
+fn print_spans(spans: &SpanBreakdown, part_nanos: f64) {
+    for line in spans.format(part_nanos) {
+        println!("  {ANSI_ITALIC}{line}{ANSI_RESET}");
+    }
+}
+
+/// The file the spans of the first execution of every part are exported to, selected with `--trace <path>`.
+fn trace_path() -> Option<PathBuf> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|arg| arg == "--trace")?;
+    args.get(index + 1).map(PathBuf::from)
+}
+
+fn print_panic(panic: &PartPanic, part: &str) {
+    print!("\r");
+    println!("{part}: ⚠ {ANSI_ITALIC}{panic}{ANSI_RESET}");
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. the answer isn't known to be wrong from the submission log of the day.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    puzzle: PuzzleId,
+    id: PartId,
+) -> Option<Result<String, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -151,17 +793,71 @@ fn submit_result<T: Display>(
         process::exit(1);
     };
 
-    if part_submit != part {
+    if part_submit != id.part {
         return None;
     }
 
-    if aoc_cli::check().is_err() {
+    // NOTE: `--submit` selects the part of the day, which may be submitted as another part of the puzzle.
+    let Some(part) = id.submit else {
         eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            "Refusing to submit {}: it is not mapped to a part of the puzzle.",
+            id.label()
         );
         process::exit(1);
+    };
+
+    if !InputSource::from_args(&args).is_ok_and(|source| source.is_puzzle()) {
+        eprintln!("Refusing to submit part {part}: the answer is not the one of the puzzle input.");
+        process::exit(1);
+    }
+
+    if args.contains(&"--param".into()) {
+        eprintln!(
+            "Refusing to submit part {part}: the answer was computed with overridden parameters."
+        );
+        process::exit(1);
+    }
+
+    let result = result.to_string();
+
+    let log = SubmissionLog::read(puzzle).unwrap_or_else(|e| {
+        eprintln!("Failed to read the submission log of {puzzle}: {e}");
+        process::exit(1);
+    });
+
+    if let Err(reason) = log.check(part, &result) {
+        eprintln!("Refusing to submit part {part}: {reason}");
+        process::exit(1);
+    }
+
+    println!("Submitting result...");
+    let output = aoc_client::submit(puzzle, part, &result);
+
+    if let Err(e) = &output {
+        eprintln!("Failed to submit part {part}: {e}");
+    }
+
+    if let Ok(response) = &output {
+        let verdict = Verdict::from_response(response);
+
+        let submission = Submission {
+            part,
+            answer: result.clone(),
+            timestamp: unix_timestamp(),
+            verdict,
+        };
+
+        if let Err(e) = SubmissionLog::append(puzzle, &submission) {
+            eprintln!("Failed to log submission: {e}");
+        }
+
+        if verdict == Verdict::Correct {
+            match answers::record(puzzle, part, &result) {
+                Ok(_) => println!("Recorded answer in data/{}/answers.json.", puzzle.year),
+                Err(e) => eprintln!("Failed to record answer: {e}"),
+            }
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(output)
 }
@@ -0,0 +1,549 @@
+/// Timing of phases inside a part, marked with [`span!`](crate::span). Spans are only recorded while the runner
+/// collects them, otherwise entering a span costs a single atomic load. Recording a span takes no lock and doesn't
+/// allocate while benching, so spans don't trip `--assert-no-alloc`.
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    fs, io,
+    path::Path,
+    sync::{
+        Mutex, OnceLock,
+        atomic::{AtomicU8, AtomicU64, AtomicUsize, Ordering},
+    },
+    time::Instant,
+};
+use tinyjson::JsonValue;
+
+use crate::template::stats::format_nanos;
+
+const OFF: u8 = 0;
+const TOTALS: u8 = 1;
+const EVENTS: u8 = 2;
+
+/// What entered spans record, one of `OFF`, `TOTALS` or `EVENTS`.
+static MODE: AtomicU8 = AtomicU8::new(OFF);
+
+/// Maximum number of distinct spans that are summed up, further spans are left out of the breakdown.
+const MAX_SPANS: usize = 64;
+
+/// Maximum number of spans recorded by a single trace, further spans are left out of the trace.
+const MAX_TRACED_SPANS: usize = 1 << 16;
+
+/// Summed durations per span. Spans are summed up inside the benchmark loop, so this takes no lock and doesn't
+/// allocate: a slot is claimed by the first span of a name and keeps the name for the lifetime of the process.
+static SPAN_SLOTS: [SpanSlot; MAX_SPANS] = [const { SpanSlot::new() }; MAX_SPANS];
+
+/// Number of claimed slots, may exceed [`MAX_SPANS`] once all slots are taken.
+static CLAIMED_SLOTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Spans recorded by the current trace. Reserved before the traced execution, so that recording doesn't allocate.
+static SPAN_EVENTS: Mutex<Vec<RecordedSpan>> = Mutex::new(Vec::new());
+
+static NEXT_THREAD: AtomicU64 = AtomicU64::new(1);
+
+/// Orders the spans of a breakdown by when they were first entered.
+static NEXT_ENTRY: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Nesting of spans on the current thread.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// Small, stable thread ids for trace exports, `std::thread::ThreadId` has no numeric value.
+    static THREAD: u64 = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Time a phase of a part. Evaluates to the value of the block:
+///
+/// ```ignore
+/// let grid = span!("build_grid", { build_valid_grid(&points) });
+/// ```
+///
+/// In bench mode, `cargo time` prints the average time of each span under its part. `cargo solve --trace <path>`
+/// exports the spans of the first execution of every part as a trace.
+#[macro_export]
+macro_rules! span {
+    ($name:expr, $body:expr) => {{
+        let _span = $crate::template::Span::enter($name);
+        $body
+    }};
+}
+
+/// A span that is recorded when dropped. Created by [`span!`](crate::span).
+pub struct Span {
+    name: &'static str,
+    /// `None` if spans are not collected.
+    start: Option<Instant>,
+    depth: usize,
+    entry: u64,
+}
+
+impl Span {
+    pub fn enter(name: &'static str) -> Self {
+        if MODE.load(Ordering::Relaxed) == OFF {
+            return Span {
+                name,
+                start: None,
+                depth: 0,
+                entry: 0,
+            };
+        }
+
+        let depth = DEPTH.with(|depth| depth.replace(depth.get() + 1));
+
+        Span {
+            name,
+            start: Some(Instant::now()),
+            depth,
+            entry: NEXT_ENTRY.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+
+        let nanos = start.elapsed().as_nanos();
+        DEPTH.with(|depth| depth.set(self.depth));
+
+        match MODE.load(Ordering::Relaxed) {
+            TOTALS => {
+                if let Some(slot) = SpanSlot::claim(self.name) {
+                    slot.add(nanos, self.entry, self.depth);
+                }
+            }
+            EVENTS => {
+                if let Ok(mut events) = SPAN_EVENTS.lock()
+                    && events.len() < events.capacity()
+                {
+                    events.push(RecordedSpan {
+                        name: self.name,
+                        start: start.duration_since(epoch()).as_nanos(),
+                        nanos,
+                        thread: THREAD.with(|thread| *thread),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The running sum of a span, see [`SPAN_SLOTS`].
+struct SpanSlot {
+    name: OnceLock<&'static str>,
+    nanos: AtomicU64,
+    count: AtomicU64,
+    /// The entry of the first execution of the span, with its depth in the lowest byte.
+    first: AtomicU64,
+}
+
+impl SpanSlot {
+    const fn new() -> Self {
+        SpanSlot {
+            name: OnceLock::new(),
+            nanos: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+            first: AtomicU64::new(u64::MAX),
+        }
+    }
+
+    /// The slot of a span, claims a free slot for spans that were not summed up before.
+    /// Spans entered concurrently for the first time may claim a slot each, they are merged by [`collect_totals`].
+    fn claim(name: &'static str) -> Option<&'static SpanSlot> {
+        let claimed = CLAIMED_SLOTS.load(Ordering::Acquire).min(MAX_SPANS);
+        if let Some(slot) = SPAN_SLOTS[..claimed]
+            .iter()
+            .find(|slot| slot.name.get() == Some(&name))
+        {
+            return Some(slot);
+        }
+
+        let slot = SPAN_SLOTS.get(CLAIMED_SLOTS.fetch_add(1, Ordering::AcqRel))?;
+        let _ = slot.name.set(name);
+        Some(slot)
+    }
+
+    fn add(&self, nanos: u128, entry: u64, depth: usize) {
+        #[allow(clippy::cast_possible_truncation)]
+        self.nanos.fetch_add(nanos as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.first
+            .fetch_min(entry << 8 | depth.min(0xff) as u64, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        self.nanos.store(0, Ordering::Relaxed);
+        self.count.store(0, Ordering::Relaxed);
+        self.first.store(u64::MAX, Ordering::Relaxed);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The summed duration of a span over all executions of a part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpanTotal {
+    pub name: &'static str,
+    /// Nesting of the span when it was first entered, `0` for spans that are not inside another span.
+    pub depth: usize,
+    pub nanos: u128,
+    /// How often the span was entered.
+    pub count: u64,
+    /// When the span was first entered, relative to other spans.
+    entry: u64,
+}
+
+/// Collects spans while alive, and stops collecting when dropped, also if the part panics.
+struct Collecting;
+
+impl Collecting {
+    fn start(mode: u8) -> Self {
+        MODE.store(mode, Ordering::Relaxed);
+        Collecting
+    }
+}
+
+impl Drop for Collecting {
+    fn drop(&mut self) {
+        MODE.store(OFF, Ordering::Relaxed);
+    }
+}
+
+/// Run `func` while summing up the durations of its spans.
+pub fn collect_totals<T>(func: impl FnOnce() -> T) -> (T, Vec<SpanTotal>) {
+    SPAN_SLOTS.iter().for_each(SpanSlot::reset);
+    let collecting = Collecting::start(TOTALS);
+    let result = func();
+    drop(collecting);
+
+    let mut totals: Vec<SpanTotal> = vec![];
+
+    for slot in &SPAN_SLOTS {
+        let (Some(&name), count) = (slot.name.get(), slot.count.load(Ordering::Relaxed)) else {
+            continue;
+        };
+        if count == 0 {
+            continue;
+        }
+
+        let nanos = u128::from(slot.nanos.load(Ordering::Relaxed));
+        let first = slot.first.load(Ordering::Relaxed);
+        let (entry, depth) = (first >> 8, (first & 0xff) as usize);
+
+        match totals.iter_mut().find(|total| total.name == name) {
+            Some(total) => {
+                total.nanos += nanos;
+                total.count += count;
+                if entry < total.entry {
+                    (total.entry, total.depth) = (entry, depth);
+                }
+            }
+            None => totals.push(SpanTotal {
+                name,
+                depth,
+                nanos,
+                count,
+                entry,
+            }),
+        }
+    }
+
+    totals.sort_by_key(|total| total.entry);
+
+    (result, totals)
+}
+
+/// Run `func` without recording its spans, e.g. the setup of a part while the spans of the part are collected.
+pub fn suspended<T>(func: impl FnOnce() -> T) -> T {
+    let mode = MODE.swap(OFF, Ordering::Relaxed);
+    let result = func();
+    MODE.store(mode, Ordering::Relaxed);
+    result
+}
+
+/// The spans of a benchmark run of a part.
+#[derive(Clone, Debug, Default)]
+pub struct SpanBreakdown {
+    pub totals: Vec<SpanTotal>,
+    /// How often the part was executed while the spans were collected.
+    pub executions: u128,
+}
+
+impl SpanBreakdown {
+    /// Format the average duration of each span per execution of the part, indented by nesting.
+    /// `part_nanos` is the mean duration of the part, each span shows its share of it.
+    pub fn format(&self, part_nanos: f64) -> Vec<String> {
+        format_breakdown(&self.totals, self.executions, part_nanos)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_breakdown(totals: &[SpanTotal], executions: u128, part_nanos: f64) -> Vec<String> {
+    let names: Vec<String> = totals
+        .iter()
+        .map(|total| format!("{}{}", "  ".repeat(total.depth), total.name))
+        .collect();
+    let width = names
+        .iter()
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    totals
+        .iter()
+        .zip(names)
+        .map(|(total, name)| {
+            let nanos = total.nanos as f64 / executions.max(1) as f64;
+            let share = if part_nanos > 0.0 {
+                nanos / part_nanos * 100.0
+            } else {
+                0.0
+            };
+            format!("{name:<width$}  {:>10}  {share:>5.1}%", format_nanos(nanos))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A recorded span, as an event of the Chrome trace-event format.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpanEvent {
+    pub name: String,
+    /// Groups the events of a part, e.g. `2025/09 Part 2`.
+    pub category: Option<String>,
+    /// Nanoseconds since the first span of the process.
+    pub start: u128,
+    pub nanos: u128,
+    pub thread: u64,
+}
+
+/// A span recorded while tracing, turned into a [`SpanEvent`] once the traced execution finished.
+struct RecordedSpan {
+    name: &'static str,
+    start: u128,
+    nanos: u128,
+    thread: u64,
+}
+
+/// All traced events of the process, the trace file is rewritten with them after every part.
+static TRACE: Mutex<Vec<SpanEvent>> = Mutex::new(Vec::new());
+
+fn epoch() -> Instant {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    *EPOCH.get_or_init(Instant::now)
+}
+
+/// Run `func` while recording its spans, and add them to the trace of the process. The execution itself is traced
+/// as an enclosing event named `name`.
+/// At most [`MAX_TRACED_SPANS`] spans are recorded, the buffer for them is reserved before the execution.
+pub fn trace<T>(name: &str, func: impl FnOnce() -> T) -> T {
+    if let Ok(mut events) = SPAN_EVENTS.lock() {
+        events.clear();
+        events.reserve_exact(MAX_TRACED_SPANS);
+    }
+
+    let epoch = epoch();
+    let start = Instant::now();
+    let collecting = Collecting::start(EVENTS);
+    let result = func();
+    drop(collecting);
+    let nanos = start.elapsed().as_nanos();
+
+    let recorded = SPAN_EVENTS
+        .lock()
+        .map(|mut events| std::mem::take(&mut *events))
+        .unwrap_or_default();
+
+    if recorded.len() == recorded.capacity() {
+        eprintln!(
+            "The trace of {name} is truncated to its first {} spans.",
+            recorded.len()
+        );
+    }
+
+    let mut events = vec![SpanEvent {
+        name: name.to_string(),
+        category: Some(name.to_string()),
+        start: start.duration_since(epoch).as_nanos(),
+        nanos,
+        thread: THREAD.with(|thread| *thread),
+    }];
+
+    events.extend(recorded.into_iter().map(|span| SpanEvent {
+        name: span.name.to_string(),
+        category: Some(name.to_string()),
+        start: span.start,
+        nanos: span.nanos,
+        thread: span.thread,
+    }));
+
+    if let Ok(mut trace) = TRACE.lock() {
+        trace.extend(events);
+    }
+
+    result
+}
+
+/// Write the trace of the process to `path`, which can be opened in a trace viewer like Perfetto or `chrome://tracing`.
+pub fn write_trace(path: &Path) -> io::Result<()> {
+    let events = TRACE.lock().map(|trace| trace.clone()).unwrap_or_default();
+    fs::write(path, trace_json(&events))
+}
+
+/// Serialize events in the trace-event format, as complete (`X`) events with timestamps in microseconds.
+#[allow(clippy::cast_precision_loss)]
+fn trace_json(events: &[SpanEvent]) -> String {
+    let events = events
+        .iter()
+        .map(|event| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("name".into(), JsonValue::String(event.name.clone()));
+            map.insert(
+                "cat".into(),
+                JsonValue::String(event.category.clone().unwrap_or_default()),
+            );
+            map.insert("ph".into(), JsonValue::String("X".into()));
+            map.insert("ts".into(), JsonValue::Number(event.start as f64 / 1000.0));
+            map.insert("dur".into(), JsonValue::Number(event.nanos as f64 / 1000.0));
+            map.insert("pid".into(), JsonValue::Number(1.0));
+            map.insert("tid".into(), JsonValue::Number(event.thread as f64));
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("traceEvents".into(), JsonValue::Array(events));
+    map.insert("displayTimeUnit".into(), JsonValue::String("ms".into()));
+
+    JsonValue::Object(map)
+        .stringify()
+        .expect("trace only contains finite numbers")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{SpanEvent, SpanTotal, collect_totals, format_breakdown, suspended, trace_json};
+    use std::{collections::HashMap, str::FromStr, sync::Mutex};
+    use tinyjson::JsonValue;
+
+    /// Spans are collected process-wide, tests that collect them can't run in parallel.
+    static COLLECTING: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn sums_up_spans() {
+        let _lock = COLLECTING.lock().unwrap();
+        let (result, totals) = collect_totals(|| {
+            (0..3)
+                .map(|i| crate::span!("outer", { crate::span!("inner", { i * 2 }) }))
+                .sum::<u32>()
+        });
+
+        assert_eq!(result, 6);
+        let spans: Vec<_> = totals
+            .iter()
+            .map(|total| (total.name, total.depth, total.count))
+            .collect();
+        assert_eq!(spans, vec![("outer", 0, 3), ("inner", 1, 3)]);
+    }
+
+    #[test]
+    fn sums_up_spans_of_threads() {
+        let _lock = COLLECTING.lock().unwrap();
+        let ((), totals) = collect_totals(|| {
+            std::thread::scope(|scope| {
+                for _ in 0..4 {
+                    scope.spawn(|| (0..100).for_each(|_| crate::span!("worker", {})));
+                }
+            });
+        });
+
+        assert_eq!(totals.len(), 1);
+        assert_eq!((totals[0].name, totals[0].count), ("worker", 400));
+
+        // slots keep their names, but start from zero for every collection.
+        let ((), totals) = collect_totals(|| crate::span!("worker", {}));
+        assert_eq!(totals[0].count, 1);
+    }
+
+    #[test]
+    fn ignores_spans_outside_of_collection() {
+        let _lock = COLLECTING.lock().unwrap();
+        assert_eq!(crate::span!("parse", { 42 }), 42);
+        let ((), totals) = collect_totals(|| {});
+        assert!(totals.is_empty());
+    }
+
+    #[test]
+    fn skips_suspended_spans() {
+        let _lock = COLLECTING.lock().unwrap();
+        let ((), totals) = collect_totals(|| {
+            let grid = suspended(|| crate::span!("parse", { vec![1, 2, 3] }));
+            crate::span!("solve", { grid.into_iter().sum::<u32>() });
+        });
+
+        let names: Vec<_> = totals.iter().map(|total| total.name).collect();
+        assert_eq!(names, vec!["solve"]);
+    }
+
+    #[test]
+    fn formats_breakdowns() {
+        let totals = [
+            SpanTotal {
+                name: "parse",
+                depth: 0,
+                nanos: 2_000_000,
+                count: 10,
+                entry: 0,
+            },
+            SpanTotal {
+                name: "flood_fill",
+                depth: 1,
+                nanos: 50_000_000,
+                count: 10,
+                entry: 1,
+            },
+        ];
+
+        assert_eq!(
+            format_breakdown(&totals, 10, 10_000_000.0),
+            vec![
+                "parse            200.0µs    2.0%",
+                "  flood_fill       5.0ms   50.0%",
+            ]
+        );
+    }
+
+    #[test]
+    fn serializes_trace_events() {
+        let json = trace_json(&[SpanEvent {
+            name: "parse".into(),
+            category: Some("2025/09 Part 2".into()),
+            start: 1_500,
+            nanos: 2_000_000,
+            thread: 1,
+        }]);
+
+        let json = JsonValue::from_str(&json).unwrap();
+        let events: &Vec<JsonValue> = json
+            .get::<HashMap<String, JsonValue>>()
+            .unwrap()
+            .get("traceEvents")
+            .unwrap()
+            .get()
+            .unwrap();
+        let event: &HashMap<String, JsonValue> = events[0].get().unwrap();
+
+        assert_eq!(event.get("ph").unwrap().get::<String>().unwrap(), "X");
+        assert_eq!(*event.get("ts").unwrap().get::<f64>().unwrap(), 1.5);
+        assert_eq!(*event.get("dur").unwrap().get::<f64>().unwrap(), 2000.0);
+        assert_eq!(
+            event.get("cat").unwrap().get::<String>().unwrap(),
+            "2025/09 Part 2"
+        );
+    }
+}
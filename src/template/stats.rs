@@ -0,0 +1,215 @@
+/// Summary statistics of benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Distribution of the per-iteration time of a benched part, in nanoseconds.
+///
+/// `mean` and `stddev` exclude outliers outside of Tukey's fences (1.5 × IQR),
+/// `min`, `median` and `p95` are computed over all samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+    pub p95: f64,
+    pub outliers: u64,
+}
+
+impl BenchStats {
+    /// Compute statistics from a non-empty set of samples.
+    pub fn from_samples(samples: &mut [f64]) -> Self {
+        assert!(!samples.is_empty(), "expected at least one sample");
+
+        samples.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(samples, 25.0);
+        let q3 = percentile(samples, 75.0);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let inliers: Vec<f64> = samples
+            .iter()
+            .copied()
+            .filter(|x| (low..=high).contains(x))
+            .collect();
+
+        let mean = average(&inliers);
+
+        let stddev = if inliers.len() > 1 {
+            let variance = inliers.iter().map(|x| (x - mean).powi(2)).sum::<f64>()
+                / (inliers.len() - 1) as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+
+        Self {
+            min: samples[0],
+            median: percentile(samples, 50.0),
+            mean,
+            stddev,
+            p95: percentile(samples, 95.0),
+            outliers: (samples.len() - inliers.len()) as u64,
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn average(numbers: &[f64]) -> f64 {
+    numbers.iter().sum::<f64>() / numbers.len() as f64
+}
+
+/// Linearly interpolated percentile `p` (0 to 100) of sorted samples.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Format nanoseconds the same way the runner prints durations.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Parse a duration formatted by [`format_nanos`] back into nanoseconds, e.g. `71.8µs`.
+pub fn parse_nanos(formatted: &str) -> Option<f64> {
+    let split = formatted.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = formatted.split_at(split);
+
+    let factor = match unit {
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    value.parse::<f64>().ok().map(|value| value * factor)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(BenchStats {
+            min: number("min")?,
+            median: number("median")?,
+            mean: number("mean")?,
+            stddev: number("stddev")?,
+            p95: number("p95")?,
+            outliers: number("outliers")? as u64,
+        })
+    }
+}
+
+/// Serialize optional stats, using `null` if absent.
+pub fn stats_to_json(stats: Option<&BenchStats>) -> JsonValue {
+    stats.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/// Deserialize optional stats. A missing key or `null` are treated as absent.
+pub fn stats_from_json(value: Option<&JsonValue>) -> Result<Option<BenchStats>, String> {
+    match value {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(v) => BenchStats::try_from(v).map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchStats, format_nanos, parse_nanos, percentile};
+
+    #[test]
+    fn interpolates_percentiles() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 50.0), 3.0);
+        assert_eq!(percentile(&sorted, 100.0), 5.0);
+        assert_eq!(percentile(&sorted, 95.0), 4.8);
+    }
+
+    #[test]
+    fn computes_stats() {
+        let mut samples = [12.0, 10.0, 14.0, 10.0, 14.0, 12.0];
+        let stats = BenchStats::from_samples(&mut samples);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.median, 12.0);
+        assert_eq!(stats.mean, 12.0);
+        assert_eq!(stats.stddev, 1.7888543819998317);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn excludes_outliers_from_mean() {
+        let mut samples = [10.0, 11.0, 10.0, 11.0, 10.0, 11.0, 10.0, 11.0, 1000.0];
+        let stats = BenchStats::from_samples(&mut samples);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.mean, 10.5);
+        assert_eq!(stats.min, 10.0);
+        assert!(stats.p95 > 11.0);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&mut [42.0]);
+        assert_eq!(stats.mean, 42.0);
+        assert_eq!(stats.stddev, 0.0);
+        assert_eq!(stats.p95, 42.0);
+    }
+
+    #[test]
+    fn formats_nanos() {
+        assert_eq!(format_nanos(74.13), "74.0ns");
+        assert_eq!(format_nanos(71_800.0), "71.8µs");
+        assert_eq!(format_nanos(1_100_000.0), "1.1ms");
+    }
+
+    #[test]
+    fn parses_formatted_nanos() {
+        assert_eq!(parse_nanos("74.0ns"), Some(74.0));
+        assert_eq!(parse_nanos("71.8µs"), Some(71_800.0));
+        assert_eq!(parse_nanos("1.1ms"), Some(1_100_000.0));
+        assert_eq!(parse_nanos("2.0s"), Some(2_000_000_000.0));
+        assert_eq!(parse_nanos("-"), None);
+        assert_eq!(parse_nanos("12"), None);
+    }
+}
@@ -0,0 +1,336 @@
+/// Local log of the answers submitted for a day.
+/// Used to refuse submissions that are known to be wrong before they cost a lockout.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{PuzzleId, data_dir, history::format_timestamp};
+
+static SUBMISSIONS_DIR_NAME: &str = "submissions";
+
+/// The response of Advent of Code to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// The response did not judge the answer, e.g. because of a rate limit or an already solved part.
+    Unknown,
+}
+
+impl Verdict {
+    /// Parse the verdict from the response message of the website.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("That's not the right answer") {
+            if response.contains("too high") {
+                Verdict::TooHigh
+            } else if response.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::Unknown => write!(f, "no verdict"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub verdict: Verdict,
+}
+
+/// All answers submitted for a day, oldest first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SubmissionLog {
+    pub submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Read the submission log of a puzzle. If not present, returns an empty log.
+    pub fn read(puzzle: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(log_path(puzzle)) {
+            Ok(contents) => Self::parse(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        let submissions = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                JsonValue::from_str(line)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| Submission::try_from(&json))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { submissions })
+    }
+
+    /// Check whether `answer` is worth submitting for `part`, given the earlier verdicts.
+    /// Returns the reason if it is known to be wrong (or already accepted).
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), String> {
+        let judged = self
+            .submissions
+            .iter()
+            .filter(|s| s.part == part && s.verdict != Verdict::Unknown);
+
+        if let Some(previous) = judged.clone().find(|s| s.answer == answer) {
+            return Err(format!(
+                "{answer} was already submitted on {} and was {}.",
+                format_timestamp(previous.timestamp),
+                previous.verdict
+            ));
+        }
+
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(());
+        };
+
+        for previous in judged {
+            let Ok(bound) = previous.answer.trim().parse::<i128>() else {
+                continue;
+            };
+
+            let violated = match previous.verdict {
+                Verdict::TooHigh => value >= bound,
+                Verdict::TooLow => value <= bound,
+                _ => false,
+            };
+
+            if violated {
+                return Err(format!(
+                    "{answer} can't be right, {bound} was {} ({}).",
+                    previous.verdict,
+                    format_timestamp(previous.timestamp)
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Append a submission to the log of a puzzle.
+    pub fn append(puzzle: PuzzleId, submission: &Submission) -> Result<(), io::Error> {
+        fs::create_dir_all(data_dir(puzzle.year).join(SUBMISSIONS_DIR_NAME))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_path(puzzle))?;
+
+        let line = JsonValue::from(submission)
+            .stringify()
+            .map_err(io::Error::other)?;
+
+        writeln!(file, "{line}")
+    }
+}
+
+fn log_path(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_path(SUBMISSIONS_DIR_NAME, "jsonl")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>().cloned())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            part: number("part")? as u8,
+            answer,
+            timestamp: number("timestamp")? as u64,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Submission, SubmissionLog, Verdict};
+    use tinyjson::JsonValue;
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            part,
+            answer: answer.into(),
+            timestamp: 1_765_189_860,
+            verdict,
+        }
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have to wait."),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn roundtrips_log() {
+        let log = SubmissionLog {
+            submissions: vec![
+                submission(1, "42", Verdict::TooLow),
+                submission(1, "1337", Verdict::Correct),
+            ],
+        };
+
+        let contents: String = log
+            .submissions
+            .iter()
+            .map(|s| JsonValue::from(s).stringify().unwrap() + "\n")
+            .collect();
+
+        assert_eq!(SubmissionLog::parse(&contents).unwrap(), log);
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let log = SubmissionLog {
+            submissions: vec![
+                submission(1, "ABC", Verdict::Incorrect),
+                submission(1, "42", Verdict::Unknown),
+            ],
+        };
+
+        assert!(log.check(1, "ABC").is_err());
+        assert!(log.check(2, "ABC").is_ok());
+        assert!(log.check(1, "42").is_ok());
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let log = SubmissionLog {
+            submissions: vec![
+                submission(1, "100", Verdict::TooHigh),
+                submission(1, "10", Verdict::TooLow),
+                submission(2, "5", Verdict::TooHigh),
+            ],
+        };
+
+        assert!(log.check(1, "100").is_err());
+        assert!(log.check(1, "150").is_err());
+        assert!(log.check(1, "10").is_err());
+        assert!(log.check(1, "-3").is_err());
+        assert!(log.check(1, "50").is_ok());
+        assert!(log.check(1, "not a number").is_ok());
+        assert!(log.check(2, "4").is_ok());
+    }
+}
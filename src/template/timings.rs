@@ -1,20 +1,95 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    Day, PuzzleId, Year, data_dir,
+    heap::{HeapProfile, heap_from_json, heap_to_json},
+    machine::MachineProfile,
+    part_label,
+    stats::{BenchStats, format_nanos, parse_nanos, stats_from_json, stats_to_json},
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_DIR_NAME: &str = "timings";
+
+/// Timings were stored in a single file before they were stored per machine.
+static LEGACY_TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// The parts that were run, ordered by part.
+    pub parts: Vec<PartTiming>,
     pub total_nanos: f64,
+    /// Allocations of the day, recorded by `cargo solve <day> --dhat`.
+    pub heap: Option<HeapProfile>,
+}
+
+/// Represents the benchmark time of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    pub part: u8,
+    /// The name of the function implementing the part, `None` in timings stored before parts were named.
+    pub name: Option<String>,
+    /// Mean execution time, `None` if the part has no answer.
+    pub nanos: Option<f64>,
+    pub stats: Option<BenchStats>,
+    /// Mean execution time of the setup of the part, excluded from `nanos`. Only present for days with a `setup`.
+    pub setup_nanos: Option<f64>,
+    /// The panic or error of the part, if it errored instead of returning an answer.
+    pub error: Option<String>,
+}
+
+impl PartTiming {
+    /// How the part is shown in the readme, e.g. `Part 1` or the name of an extra part.
+    pub fn label(&self) -> String {
+        part_label(self.part, self.name.as_deref())
+    }
+
+    /// The formatted mean execution time, e.g. `1.2ms`.
+    pub fn formatted(&self) -> Option<String> {
+        self.nanos.map(format_nanos)
+    }
+
+    /// Mean execution time including the setup, which is what the part cost before days could have a setup.
+    pub fn total_nanos(&self) -> Option<f64> {
+        self.nanos
+            .map(|nanos| nanos + self.setup_nanos.unwrap_or_default())
+    }
 }
 
-/// Represents benchmark times for a set of days.
+impl Timing {
+    /// A day without timed parts.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parts: vec![],
+            total_nanos: 0_f64,
+            heap: None,
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        self.parts.iter().find(|timing| timing.part == part)
+    }
+
+    /// The panic or error of a part, `None` unless it errored.
+    pub fn part_error(&self, part: u8) -> Option<&str> {
+        self.part(part).and_then(|timing| timing.error.as_deref())
+    }
+
+    /// Mean execution time of a part in nanoseconds, `None` if it was not benched.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        self.part(part).and_then(|timing| timing.nanos)
+    }
+
+    /// Mean execution time of a part including its setup, see [`PartTiming::total_nanos`].
+    pub fn part_total_nanos(&self, part: u8) -> Option<f64> {
+        self.part(part).and_then(PartTiming::total_nanos)
+    }
+}
+
+/// Represents benchmark times for a set of days, benched on the same machine.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
@@ -22,27 +97,69 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
-        let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
-        json.format_to(&mut file)
+    /// Dehydrate the timings of a machine to its JSON file, e.g. `data/2025/timings/<machine>.json`.
+    pub fn store_file(&self, year: Year, machine: &MachineProfile) -> Result<(), Error> {
+        let mut json = JsonValue::from(self.clone());
+        if let JsonValue::Object(map) = &mut json {
+            map.insert("machine".into(), JsonValue::from(machine));
+        }
+
+        fs::create_dir_all(data_dir(year).join(TIMINGS_DIR_NAME))?;
+        let mut file = fs::File::create(timings_path(year, machine))?;
+        json.format_to(&mut file)?;
+
+        // NOTE: legacy timings were read as the timings of this machine, they are migrated now.
+        let _ = fs::remove_file(data_dir(year).join(LEGACY_TIMINGS_FILE_NAME));
+        Ok(())
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate the timings of a machine from its JSON file. If not present, falls back to the legacy timings
+    /// file of the year, otherwise returns empty timings.
+    pub fn read_from_file(year: Year, machine: &MachineProfile) -> Self {
+        fs::read_to_string(timings_path(year, machine))
+            .or_else(|_| fs::read_to_string(data_dir(year).join(LEGACY_TIMINGS_FILE_NAME)))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
+    /// Rehydrate the timings of all machines of a year, ordered by machine.
+    pub fn read_all(year: Year) -> Vec<(MachineProfile, Timings)> {
+        let Ok(entries) = fs::read_dir(data_dir(year).join(TIMINGS_DIR_NAME)) else {
+            return vec![];
+        };
+
+        let mut machines: Vec<(MachineProfile, Timings)> = entries
+            .filter_map(|entry| {
+                let contents = fs::read_to_string(entry.ok()?.path()).ok()?;
+                let json = JsonValue::from_str(&contents).ok()?;
+                let machine = json
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get("machine")
+                    .and_then(|machine| MachineProfile::try_from(machine).ok())?;
+                Some((machine, Timings::try_from(contents).ok()?))
+            })
+            .collect();
+
+        machines.sort_by_key(|(machine, _)| machine.id());
+        machines
+    }
+
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Heap profiles are kept unless `other` has one, since benchmark runs don't profile the heap.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if timing.heap.is_none() {
+                timing.heap = self
+                    .data
+                    .iter()
+                    .find(|t| t.day == timing.day)
+                    .and_then(|t| t.heap);
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,22 +168,45 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Record the heap profile of a day, keeping its benchmark times.
+    pub fn set_heap(&mut self, day: Day, heap: HeapProfile) {
+        match self.data.iter_mut().find(|t| t.day == day) {
+            Some(timing) => timing.heap = Some(heap),
+            None => {
+                self.data.push(Timing {
+                    heap: Some(heap),
+                    ..Timing::new(day)
+                });
+                self.data.sort_unstable_by_key(|t| t.day);
+            }
+        }
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+    /// Whether all parts of a puzzle are benched. The final day of a year may not have a part two.
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
+        self.data.iter().any(|t| {
+            t.day == puzzle.day
+                && t.part_nanos(1).is_some()
+                && (t.part_nanos(2).is_some() || !puzzle.has_part(2))
+        })
     }
 }
 
+fn timings_path(year: Year, machine: &MachineProfile) -> PathBuf {
+    data_dir(year)
+        .join(TIMINGS_DIR_NAME)
+        .join(format!("{}.json", machine.id()))
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -113,25 +253,11 @@ impl From<&Timing> for JsonValue {
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
-
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "parts".into(),
+            JsonValue::Array(value.parts.iter().map(JsonValue::from).collect()),
         );
+        map.insert("heap".into(), heap_to_json(value.heap.as_ref()));
 
         JsonValue::Object(map)
     }
@@ -151,26 +277,134 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let heap = heap_from_json(json.get("heap"))?;
+
+        let parts = match json.get("parts") {
+            Some(parts) => parts
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.parts to be an array.")?
+                .iter()
+                .map(PartTiming::try_from)
+                .collect::<Result<_, _>>()?,
+            None => legacy_parts(json)?,
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parts,
             total_nanos,
+            heap,
+        })
+    }
+}
+
+/// Read the parts of timings stored before days could have more than two parts, which have a set of keys per part,
+/// e.g. `part_1` with the formatted time and `part_1_stats`.
+fn legacy_parts(json: &HashMap<String, JsonValue>) -> Result<Vec<PartTiming>, String> {
+    let mut parts = vec![];
+
+    for part in [1, 2] {
+        let formatted = json
+            .get(&format!("part_{part}"))
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or(format!("Expected timing.part_{part} to be null or string."))?;
+
+        // NOTE: stats are optional to support timings stored before they were introduced.
+        let stats = stats_from_json(json.get(&format!("part_{part}_stats")))?;
+
+        // NOTE: timings stored before numeric values were introduced only have the formatted ones.
+        let nanos = json
+            .get(&format!("part_{part}_nanos"))
+            .and_then(|v| v.get::<f64>().copied())
+            .or_else(|| formatted.and_then(|s| parse_nanos(s)));
+
+        // NOTE: errors are optional to support timings stored before parts could error.
+        let error = json
+            .get(&format!("part_{part}_error"))
+            .and_then(|v| v.get::<String>().cloned());
+
+        if nanos.is_some() || error.is_some() {
+            parts.push(PartTiming {
+                part,
+                name: None,
+                nanos,
+                stats,
+                setup_nanos: None,
+                error,
+            });
+        }
+    }
+
+    Ok(parts)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "name".into(),
+            value
+                .name
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "nanos".into(),
+            value.nanos.map_or(JsonValue::Null, JsonValue::Number),
+        );
+        map.insert("stats".into(), stats_to_json(value.stats.as_ref()));
+        map.insert(
+            "setup_nanos".into(),
+            value.setup_nanos.map_or(JsonValue::Null, JsonValue::Number),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected part.part to be a number.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>().cloned());
+
+        Ok(PartTiming {
+            part,
+            name: string("name"),
+            nanos: json.get("nanos").and_then(|v| v.get::<f64>().copied()),
+            stats: stats_from_json(json.get("stats"))?,
+            // NOTE: optional to support timings stored before days could have a setup.
+            setup_nanos: json
+                .get("setup_nanos")
+                .and_then(|v| v.get::<f64>().copied()),
+            error: string("error"),
         })
     }
 }
@@ -179,31 +413,35 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{day, template::Day};
+
+    use super::{PartTiming, Timing, Timings};
+
+    fn get_mock_part(part: u8, nanos: Option<f64>) -> PartTiming {
+        PartTiming {
+            part,
+            name: None,
+            nanos,
+            stats: None,
+            setup_nanos: None,
+            error: None,
+        }
+    }
 
-    use super::{Timing, Timings};
+    fn get_mock_timing(day: Day, part_1: Option<f64>, part_2: Option<f64>) -> Timing {
+        Timing {
+            parts: vec![get_mock_part(1, part_1), get_mock_part(2, part_2)],
+            total_nanos: part_1.unwrap_or_default() + part_2.unwrap_or_default(),
+            ..Timing::new(day)
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
+                get_mock_timing(day!(1), Some(1e+7), Some(2e+7)),
+                get_mock_timing(day!(2), Some(3e+7), Some(4e+7)),
+                get_mock_timing(day!(4), Some(4e+7), None),
             ],
         }
     }
@@ -213,14 +451,61 @@ mod tests {
 
         #[test]
         fn handles_json_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parts": [{ "part": 1, "name": "part_one", "nanos": 1000000, "stats": null, "error": null }], "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 1);
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.part(1).unwrap().name, Some("part_one".into()));
+            assert_eq!(timing.part(1).unwrap().formatted(), Some("1.0ms".into()));
+            assert_eq!(timing.part(2), None);
+            assert_eq!(timing.total_nanos, 1_000_000_f64);
+        }
+
+        #[test]
+        fn handles_legacy_json_timings() {
             let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
-            assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_nanos(1), Some(1_000_000_f64));
+            assert_eq!(timing.part(1).unwrap().name, None);
+            assert_eq!(timing.part(2), None);
+        }
+
+        #[test]
+        fn prefers_numeric_part_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "71.8µs", "part_2": "72.5µs", "part_1_nanos": 71812.5, "total_nanos": 144312.5 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_nanos(1), Some(71_812.5));
+            assert_eq!(timing.part_nanos(2), Some(72_500_f64));
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "min": 900000, "median": 990000, "mean": 1000000, "stddev": 5000.5, "p95": 1100000, "outliers": 2 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part(1).unwrap().stats.unwrap();
+            assert_eq!(stats.median, 990_000_f64);
+            assert_eq!(stats.stddev, 5000.5);
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(timing.part(2), None);
+        }
+
+        #[test]
+        fn handles_errored_parts() {
+            let json = r#"{ "data": [{ "day": "08", "part_1": null, "part_2": "46.7ms", "part_1_error": "panicked at src/bin/2025_08.rs:12:34: index out of bounds", "total_nanos": 46700000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_error(1),
+                Some("panicked at src/bin/2025_08.rs:12:34: index out of bounds")
+            );
+            assert_eq!(timing.part_error(2), None);
         }
 
         #[test]
@@ -243,10 +528,18 @@ mod tests {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_parts() {
+            let json = r#"{ "data": [{ "day": "01", "parts": [{ "name": "part_one" }], "total_nanos": 0 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
     }
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{PartTiming, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,60 +559,84 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_extra_parts() {
+            let mut timings = get_mock_timings();
+            timings.data[0].parts.push(PartTiming {
+                part: 3,
+                name: Some("visualise".into()),
+                nanos: Some(5e+6),
+                stats: None,
+                setup_nanos: Some(2e+6),
+                error: None,
+            });
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].parts, timings.data[0].parts);
+            assert_eq!(parsed.data[0].parts[2].label(), "visualise");
+            assert_eq!(parsed.data[0].part_total_nanos(3), Some(7e+6));
+        }
     }
 
     mod is_day_complete {
-        use crate::{
-            day,
-            template::timings::{Timing, Timings},
-        };
+        use crate::{day, template::PuzzleId, template::timings::Timings, year};
+
+        use super::get_mock_timing;
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
-                }],
+                data: vec![get_mock_timing(day!(1), Some(1e+6), Some(2e+6))],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(
+                timings.is_day_complete(PuzzleId::new(year!(2025), day!(1)).unwrap()),
+                true
+            );
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
-                }],
+                data: vec![get_mock_timing(day!(1), Some(1e+6), None)],
+            };
+
+            assert_eq!(
+                timings.is_day_complete(PuzzleId::new(year!(2025), day!(1)).unwrap()),
+                false
+            );
+        }
+
+        #[test]
+        fn handles_final_day_without_part_two() {
+            let timings = Timings {
+                data: vec![get_mock_timing(day!(12), Some(1e+6), None)],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            let puzzle = |year| PuzzleId::new(year, day!(12)).unwrap();
+            assert_eq!(timings.is_day_complete(puzzle(year!(2025))), true);
+            assert_eq!(timings.is_day_complete(puzzle(year!(2024))), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![get_mock_timing(day!(1), None, None)],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(
+                timings.is_day_complete(PuzzleId::new(year!(2025), day!(1)).unwrap()),
+                false
+            );
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::heap::HeapProfile,
             template::timings::{Timing, Timings},
         };
 
@@ -329,12 +646,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +661,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 
@@ -380,5 +687,26 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_heap_profiles() {
+            let heap = HeapProfile {
+                total_bytes: 2048,
+                total_blocks: 4,
+                peak_bytes: 1024,
+            };
+
+            let mut timings = get_mock_timings();
+            timings.set_heap(day!(2), heap);
+            timings.set_heap(day!(3), heap);
+            assert_eq!(timings.data.len(), 4);
+            assert_eq!(timings.data[1].part_nanos(1), Some(3e+7));
+            assert_eq!(timings.data[2].day, day!(3));
+
+            let merged = timings.merge(&get_mock_timings());
+            assert_eq!(merged.data[1].heap, Some(heap));
+            assert_eq!(merged.data[2].heap, Some(heap));
+            assert_eq!(merged.data[0].heap, None);
+        }
     }
 }
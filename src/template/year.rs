@@ -0,0 +1,116 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// A year of advent (i.e. an integer starting from 2015, the first event).
+///
+/// # Display
+/// This value displays as a four digit number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if an event took place in it,
+    /// returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The puzzles released in this year.
+    pub const fn calendar(self) -> Calendar {
+        // NOTE: starting with 2025, advent has 12 puzzles instead of 25.
+        let days = if self.0 >= 2025 { 12 } else { 25 };
+
+        Calendar {
+            days,
+            final_day_has_part_two: false,
+        }
+    }
+
+    /// The default year, configured by the `AOC_YEAR` environment variable in `.cargo/config.toml`.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+/// The puzzles released in a year of advent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calendar {
+    /// The number of days with a puzzle, starting on the 1st of december.
+    pub days: u8,
+    /// Whether the puzzle of the final day has a second part.
+    /// Usually, its second star is awarded for completing all other puzzles instead.
+    pub final_day_has_part_two: bool,
+}
+
+impl Calendar {
+    /// Whether `day` has a puzzle.
+    pub const fn has_day(&self, day: Day) -> bool {
+        day.into_inner() <= self.days
+    }
+
+    /// Whether `part` of the puzzle of `day` exists.
+    pub const fn has_part(&self, day: Day, part: u8) -> bool {
+        if !self.has_day(day) {
+            return false;
+        }
+        match part {
+            1 => true,
+            2 => day.into_inner() < self.days || self.final_day_has_part_two,
+            _ => false,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year from 2015 onwards")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {
+        const {
+            $crate::template::Year::new($year)
+                .expect("invalid year, expecting a value from 2015 onwards")
+        }
+    };
+}